@@ -12,6 +12,7 @@ pub struct InstantiateMsg {
 #[cw_ownable_execute]
 #[cw_serde]
 #[derive(cw_orch::ExecuteFns)] // Functio
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Instantiates a new vesting contract that is funded by a native token.
     CreateNativeAvEventContract {
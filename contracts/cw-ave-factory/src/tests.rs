@@ -3,7 +3,7 @@ use cosmwasm_std::testing::mock_dependencies;
 use cosmwasm_std::{coins, Addr, Api, CanonicalAddr, Coin, Empty, Timestamp, Uint128};
 use cw4::Member;
 use cw_ave::msg::InstantiateMsg as AvEventInstantiateMsg;
-use cw_ave::state::{EventSegment, EventSegmentAccessType, GuestDetails};
+use cw_ave::state::{EventSegment, EventSegmentAccessType, GuestDetails, ReentryRule};
 use cw_ave::ContractError as CwAveContractError;
 use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
 use cw_ownable::OwnershipError;
@@ -89,6 +89,9 @@ fn create_valid_ave_instantiate_msg(cw420_code_id: u64) -> AvEventInstantiateMsg
                     denom: NATIVE_DENOM.to_string(),
                     amount: Uint128::new(1000),
                 }],
+                cw20_ticket_cost: vec![],
+                overbooking_limit: 0,
+                reentry: ReentryRule::Never,
                 event_segment_access: EventSegmentAccessType::SingleSegment { id: 0u64 },
                 total_ticket_limit: 10,
             },
@@ -101,20 +104,39 @@ fn create_valid_ave_instantiate_msg(cw420_code_id: u64) -> AvEventInstantiateMsg
                     amount: Uint128::new(500),
                 }],
                 total_ticket_limit: 10,
+                cw20_ticket_cost: vec![],
+                overbooking_limit: 0,
+                reentry: ReentryRule::Never,
                 event_segment_access: EventSegmentAccessType::SingleSegment { id: 1u64 },
             },
         ],
         cw420: cw420_code_id,
+        resale: None,
+        existing_usher_group: None,
+        usher_supervisor_weight: None,
+        existing_guest_group: None,
+        poap: None,
+        draft: false,
+        revenue_splits: vec![],
+        webauthn_rp_id: None,
         event_timeline: vec![
             EventSegment {
                 stage_description: "Opening".to_string(),
                 start: Timestamp::from_seconds(1000),
                 end: Timestamp::from_seconds(2000),
+                escrow_weight: None,
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
             },
             EventSegment {
                 stage_description: "Main Event".to_string(),
                 start: Timestamp::from_seconds(2000),
                 end: Timestamp::from_seconds(3000),
+                escrow_weight: None,
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
             },
         ],
     }
@@ -459,13 +481,28 @@ fn test_list_contracts_pagination() {
                     amount: Uint128::new(500),
                 }],
                 total_ticket_limit: 10,
+                cw20_ticket_cost: vec![],
+                overbooking_limit: 0,
+                reentry: ReentryRule::Never,
                 event_segment_access: EventSegmentAccessType::SingleSegment { id: 0u64 },
             }],
             cw420: cw420_code_id,
+            resale: None,
+            existing_usher_group: None,
+            usher_supervisor_weight: None,
+            existing_guest_group: None,
+            poap: None,
+            draft: false,
+            revenue_splits: vec![],
+            webauthn_rp_id: None,
             event_timeline: vec![EventSegment {
                 stage_description: "Main Event".to_string(),
                 start: Timestamp::from_seconds(1000),
                 end: Timestamp::from_seconds(2000),
+                escrow_weight: None,
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
             }],
         };
 
@@ -650,13 +687,28 @@ fn test_query_by_instantiator_reverse() {
                     amount: Uint128::new(500),
                 }],
                 total_ticket_limit: 10,
+                cw20_ticket_cost: vec![],
+                overbooking_limit: 0,
+                reentry: ReentryRule::Never,
                 event_segment_access: EventSegmentAccessType::SingleSegment { id: 0u64 },
             }],
             cw420: cw420_code_id,
+            resale: None,
+            existing_usher_group: None,
+            usher_supervisor_weight: None,
+            existing_guest_group: None,
+            poap: None,
+            draft: false,
+            revenue_splits: vec![],
+            webauthn_rp_id: None,
             event_timeline: vec![EventSegment {
                 stage_description: "Main Event".to_string(),
                 start: Timestamp::from_seconds(1000),
                 end: Timestamp::from_seconds(2000),
+                escrow_weight: None,
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
             }],
         };
 
@@ -843,19 +895,38 @@ fn test_invalid_guest_details() {
                 denom: NATIVE_DENOM.to_string(),
                 amount: Uint128::new(500),
             }],
+            cw20_ticket_cost: vec![],
+            overbooking_limit: 0,
+            reentry: ReentryRule::Never,
             event_segment_access: EventSegmentAccessType::SingleSegment { id: 0u64 },
         }],
         cw420: cw420_code_id,
+        resale: None,
+        existing_usher_group: None,
+        usher_supervisor_weight: None,
+        existing_guest_group: None,
+        poap: None,
+        draft: false,
+        revenue_splits: vec![],
+        webauthn_rp_id: None,
         event_timeline: vec![
             EventSegment {
                 stage_description: "First Event".to_string(),
                 start: Timestamp::from_seconds(1000),
                 end: Timestamp::from_seconds(2000),
+                escrow_weight: None,
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
             },
             EventSegment {
                 stage_description: "Overlapping Event".to_string(),
                 start: Timestamp::from_seconds(1500), // Overlaps with first event
                 end: Timestamp::from_seconds(2500),
+                escrow_weight: None,
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
             },
         ],
     };
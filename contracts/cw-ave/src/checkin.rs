@@ -0,0 +1,272 @@
+use cosmos_sdk_proto::{
+    cosmos::crypto::{ed25519, secp256k1, secp256r1},
+    traits::Message,
+    Any,
+};
+use cosmwasm_std::{from_json, Addr, Api, Binary, CanonicalAddr, Env, Storage, Timestamp};
+use ripemd::{Digest, Ripemd160};
+use serde::Deserialize;
+
+use crate::state::{
+    preamble_msg_arb_036, sha256, CheckInDetails, CheckInSignatureData, CheckInSignatureScheme,
+    EventSegmentAccessType, UsherScope, EVENT_STAGES, USED_CHECKIN_NONCES,
+};
+use crate::ContractError;
+
+pub const SECP256K1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+pub const SECP256R1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256r1.PubKey";
+pub const ED25519_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+/// proto message name hashed into secp256r1 addresses
+pub const SECP256R1_PUBKEY_PROTO_NAME: &str = "cosmos.crypto.secp256r1.PubKey";
+/// user present flag of WebAuthn authenticator data.
+pub const WEBAUTHN_FLAG_USER_PRESENT: u8 = 0x01;
+/// longest a checkin signature can be valid for, so a captured signature stops working shortly after.
+pub const MAX_CHECKIN_SIGNATURE_VALIDITY: u64 = 600;
+
+/// client data of a WebAuthn assertion, only the fields checked are parsed.
+#[derive(Deserialize)]
+struct WebAuthnClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+}
+
+/// decodes the key of a protobuf `Any` encoded pubkey, for the scheme it is used with.
+pub fn decode_pubkey(
+    pubkey: &Binary,
+    scheme: &CheckInSignatureScheme,
+) -> Result<Vec<u8>, ContractError> {
+    let any = Any::decode(pubkey.as_slice()).map_err(|_| ContractError::InvalidPubkey {})?;
+    let (key, len) = match scheme {
+        CheckInSignatureScheme::Adr036Secp256k1 {} if any.type_url == SECP256K1_PUBKEY_TYPE_URL => {
+            (
+                secp256k1::PubKey::decode(any.value.as_slice()).map(|pk| pk.key),
+                33,
+            )
+        }
+        CheckInSignatureScheme::Adr036Ed25519 {} if any.type_url == ED25519_PUBKEY_TYPE_URL => (
+            ed25519::PubKey::decode(any.value.as_slice()).map(|pk| pk.key),
+            32,
+        ),
+        CheckInSignatureScheme::WebAuthnSecp256r1 { .. }
+            if any.type_url == SECP256R1_PUBKEY_TYPE_URL =>
+        {
+            (
+                secp256r1::PubKey::decode(any.value.as_slice()).map(|pk| pk.key),
+                33,
+            )
+        }
+        _ => return Err(ContractError::InvalidPubkey {}),
+    };
+    let key = key.map_err(|_| ContractError::InvalidPubkey {})?;
+    if key.len() != len {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    Ok(key)
+}
+
+/// canonical address of an account, derived from its pubkey the way the cosmos-sdk does:
+/// ripemd160(sha256(pubkey)) for secp256k1, the first 20 bytes of sha256(pubkey) for ed25519,
+/// and the 32 byte `address.Hash` of the pubkey proto name & compressed key for secp256r1,
+/// sha256(sha256("cosmos.crypto.secp256r1.PubKey") || pubkey).
+pub fn pubkey_to_canonical(pubkey: &[u8], scheme: &CheckInSignatureScheme) -> CanonicalAddr {
+    match scheme {
+        CheckInSignatureScheme::Adr036Secp256k1 {} => {
+            CanonicalAddr::from(Ripemd160::digest(sha256(pubkey)).to_vec())
+        }
+        CheckInSignatureScheme::Adr036Ed25519 {} => CanonicalAddr::from(&sha256(pubkey)[..20]),
+        CheckInSignatureScheme::WebAuthnSecp256r1 { .. } => {
+            let mut preimage = sha256(SECP256R1_PUBKEY_PROTO_NAME.as_bytes());
+            preimage.extend_from_slice(pubkey);
+            CanonicalAddr::from(sha256(&preimage))
+        }
+    }
+}
+
+/// verifies the signature of a guest over the ADR-036 sign doc of their checkin,
+/// and that the signing key belongs to `ticket_addr`.
+/// WebAuthn assertions must be made for `webauthn_rp_id`, and are refused if it is not set.
+pub fn verify_checkin_signature(
+    api: &dyn Api,
+    checkin: &CheckInDetails,
+    webauthn_rp_id: Option<&str>,
+) -> Result<(), ContractError> {
+    let scheme = checkin
+        .scheme
+        .clone()
+        .unwrap_or(CheckInSignatureScheme::Adr036Secp256k1 {});
+    let pubkey = decode_pubkey(&checkin.pubkey, &scheme)?;
+    if api.addr_canonicalize(&checkin.ticket_addr)? != pubkey_to_canonical(&pubkey, &scheme) {
+        return Err(ContractError::PubkeyTicketAddrMismatch {});
+    }
+
+    let sign_doc = preamble_msg_arb_036(&checkin.ticket_addr, &checkin.signed_data);
+    let verified = match &scheme {
+        CheckInSignatureScheme::Adr036Secp256k1 {} => {
+            api.secp256k1_verify(&sha256(sign_doc.as_bytes()), &checkin.signature, &pubkey)?
+        }
+        CheckInSignatureScheme::Adr036Ed25519 {} => {
+            api.ed25519_verify(sign_doc.as_bytes(), &checkin.signature, &pubkey)?
+        }
+        CheckInSignatureScheme::WebAuthnSecp256r1 {
+            authenticator_data,
+            client_data_json,
+        } => {
+            let client_data: WebAuthnClientData = from_json(client_data_json.as_bytes())
+                .map_err(|_| ContractError::InvalidWebAuthnClientData {})?;
+            if client_data.ty != "webauthn.get"
+                || client_data.challenge != base64url(&sha256(sign_doc.as_bytes()))
+            {
+                return Err(ContractError::InvalidWebAuthnClientData {});
+            }
+            let rp_id = webauthn_rp_id.ok_or(ContractError::WebAuthnNotEnabled {})?;
+            // authenticator data starts with the rpIdHash, followed by flags whose lowest bit is user presence
+            if authenticator_data.len() < 37
+                || authenticator_data[..32] != sha256(rp_id.as_bytes())
+                || authenticator_data[32] & WEBAUTHN_FLAG_USER_PRESENT == 0
+            {
+                return Err(ContractError::InvalidWebAuthnAuthenticatorData {});
+            }
+            // authenticators sign over the authenticator data & the hash of the client data
+            let mut signed = authenticator_data.to_vec();
+            signed.extend(sha256(client_data_json.as_bytes()));
+            api.secp256r1_verify(&sha256(&signed), &checkin.signature, &pubkey)?
+        }
+    };
+    if !verified {
+        return Err(ContractError::CheckinVerificationFailed {});
+    }
+    Ok(())
+}
+
+/// unpadded base64url encoding, as used for WebAuthn challenges
+fn base64url(data: &[u8]) -> String {
+    Binary::from(data)
+        .to_base64()
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+/// parses the base64 encoded `CheckInSignatureData` a guest signed.
+pub fn parse_checkin_signature_data(
+    signed_data: &str,
+) -> Result<CheckInSignatureData, ContractError> {
+    Ok(from_json(Binary::from_base64(signed_data)?)?)
+}
+
+/// ensures signed checkin data was made for this event & usher, has not expired,
+/// and its nonce has not been used by the ticket before.
+pub fn validate_checkin_signature_data(
+    storage: &dyn Storage,
+    env: &Env,
+    usher: &Addr,
+    ticket_addr: &String,
+    data: &CheckInSignatureData,
+) -> Result<(), ContractError> {
+    if data.event_contract_addr != env.contract.address.as_str() {
+        return Err(ContractError::CheckinWrongEventContract {});
+    }
+    if data.usher_wallet_addr != usher.as_str() {
+        return Err(ContractError::CheckinWrongUsher {});
+    }
+    if data.expires_at <= env.block.time {
+        return Err(ContractError::CheckinSignatureExpired {});
+    }
+    if data.expires_at > env.block.time.plus_seconds(MAX_CHECKIN_SIGNATURE_VALIDITY) {
+        return Err(ContractError::CheckinSignatureExpiryTooLong {
+            max_seconds: MAX_CHECKIN_SIGNATURE_VALIDITY,
+        });
+    }
+    if USED_CHECKIN_NONCES.has(storage, (ticket_addr, data.nonce)) {
+        return Err(ContractError::CheckinNonceUsed {});
+    }
+    Ok(())
+}
+
+/// event segments a guest is checked into, given the access of their guest type
+/// and the segments they signed for.
+pub fn segments_to_checkin(
+    access: &EventSegmentAccessType,
+    signed_ids: &[u64],
+) -> Result<Vec<u64>, ContractError> {
+    match access {
+        EventSegmentAccessType::SingleSegment { id } => {
+            if !signed_ids.contains(id) {
+                return Err(ContractError::IncorrectEventSegmentId {});
+            }
+            Ok(vec![*id])
+        }
+        EventSegmentAccessType::AnyOfSpecificSegments { ids } => {
+            if signed_ids.is_empty() || signed_ids.iter().any(|id| !ids.contains(id)) {
+                return Err(ContractError::IncorrectEventSegmentId {});
+            }
+            let mut checkin_ids = signed_ids.to_vec();
+            checkin_ids.sort_unstable();
+            checkin_ids.dedup();
+            Ok(checkin_ids)
+        }
+        // we just checkin all automatically
+        EventSegmentAccessType::AllOfSpecificSegments { .. } => Ok(access.segment_ids()),
+    }
+}
+
+/// ensures an usher restricted to a scope can checkin the event segments at the gate.
+pub fn ensure_within_usher_scope(
+    scope: &UsherScope,
+    segment_ids: &[u64],
+    gate: Option<&String>,
+) -> Result<(), ContractError> {
+    if !scope.segment_ids.is_empty() && segment_ids.iter().any(|id| !scope.segment_ids.contains(id))
+    {
+        return Err(ContractError::OutsideUsherScope {});
+    }
+    if !scope.gates.is_empty() && !gate.is_some_and(|gate| scope.gates.contains(gate)) {
+        return Err(ContractError::OutsideUsherScope {});
+    }
+    Ok(())
+}
+
+/// ensures a guest is checking in while each segment being checked into is open for checkin.
+/// guests with access to all of their segments at once can check in from the opening of the first,
+/// until the close of the last.
+pub fn ensure_within_checkin_windows(
+    storage: &dyn Storage,
+    access: &EventSegmentAccessType,
+    segment_ids: &[u64],
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    let mut windows = Vec::new();
+    for id in segment_ids {
+        let (opens, closes) = EVENT_STAGES.load(storage, *id)?.checkin_window();
+        windows.push((*id, opens, closes));
+    }
+
+    let (earliest, latest) = match access {
+        EventSegmentAccessType::AllOfSpecificSegments { .. } => (
+            windows.iter().min_by_key(|(_, opens, _)| *opens).copied(),
+            windows.iter().max_by_key(|(_, _, closes)| *closes).copied(),
+        ),
+        _ => (
+            windows.iter().max_by_key(|(_, opens, _)| *opens).copied(),
+            windows.iter().min_by_key(|(_, _, closes)| *closes).copied(),
+        ),
+    };
+    if let Some((segment_id, opens_at, _)) = earliest {
+        if now < opens_at {
+            return Err(ContractError::CheckinTooEarly {
+                segment_id,
+                opens_at,
+            });
+        }
+    }
+    if let Some((segment_id, _, closed_at)) = latest {
+        if now > closed_at {
+            return Err(ContractError::CheckinTooLate {
+                segment_id,
+                closed_at,
+            });
+        }
+    }
+    Ok(())
+}
@@ -1,22 +1,47 @@
+use crate::checkin::{
+    ensure_within_checkin_windows, ensure_within_usher_scope, parse_checkin_signature_data,
+    segments_to_checkin, validate_checkin_signature_data, verify_checkin_signature,
+};
 use crate::error::ContractError;
-use crate::msg::{EventSegmentRes, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::escrow::{
+    cancel_segment_escrow, cancelled_escrow_weights, escrow_status, escrow_ticket_revenue,
+    payee_revenue, refund_window_open, refundable_escrow_weights, released_escrow_weights,
+    take_expired_refunds, REFUND_WINDOW,
+};
+use crate::lifecycle::{ensure_phase, event_status, ATTENDANCE_PHASES, EDIT_PHASES, SALE_PHASES};
+use crate::msg::{
+    EventSegmentRes, ExecuteMsg, FactoryQueryMsg, GuestTypeUpdate, InstantiateMsg, MigrateMsg,
+    OccupancyRes, QueryMsg, ReceiveMsg, ResaleListingRes, SegmentSchedule, TicketAllowanceRes,
+    TicketReceiptRes, WaitlistEntryRes,
+};
 use crate::state::{
-    generate_instantiate_salt2, preamble_msg_arb_036, sha256, CheckInDetails, CheckInSignatureData,
-    Config, GuestDetails, RegisteringEventAddressAndPayment, RegisteringGuest, ReplaceHomieTicket,
-    TicketPaymentOption, ATTENDANCE_RECORD, CONFIG, EVENT_STAGES, GUEST_DETAILS, HOMIE_TICKETS,
-    LICENSE_ADDR, RESERVED_TICKETS, TOTAL_RESERVED_BY_GUEST_TYPE,
+    attendance_records, checkin_log, generate_instantiate_salt2, sha256, ticket_receipts,
+    AttendanceRecord, BatchCheckInMode, CheckInDetails, CheckInLogEntry, CheckInRecord,
+    CheckOutRecord, Config, EventPhase, EventSegment, GroupMode, GuestDetails, HomieCheckinSecret,
+    LifecycleState, RegisteringEventAddressAndPayment, RegisteringGuest, ReplaceHomieTicket,
+    ResaleListing, RevenueShare, RevenueSplit, TicketPaymentOption, TicketReceipt, UsherScope,
+    WaitlistEntry, CANCELLATION_REFUNDS, CANCELLED_SEGMENTS, CLAIMED_REVENUE, CONFIG,
+    CW20_PAYMENT_TOKENS, EARLY_OCCUPANCY, ESCROWED_REVENUE, EVENT_STAGES, GUEST_DETAILS,
+    HOMIE_CHECKIN_SECRETS, HOMIE_TICKETS, LICENSE_ADDR, NEXT_WAITLIST_POSITION, OCCUPANCY,
+    REFUND_POOL, RESALE_LISTINGS, RESERVED_TICKETS, TICKETS_SOLD, TOTAL_RESERVED_BY_GUEST_TYPE,
+    USED_CHECKIN_NONCES, USHER_SCOPES, WAITLIST, WAITLISTED_TICKETS, WAITLIST_COUNT,
+    WITHDRAWN_REVENUE_SHARES,
 };
-use av_event_helpers::{get_license_addr, LICENSE_CANONICAL_ADDR};
+use av_event_helpers::get_license_addr;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    coin, from_json, instantiate2_address, to_json_binary, Addr, BankMsg, Binary, CanonicalAddr,
-    Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
-    Storage, WasmMsg,
+    attr, coin, from_json, instantiate2_address, to_json_binary, Addr, Attribute, BankMsg, Binary,
+    BlockInfo, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw4::{Cw4QueryMsg, Member, MemberResponse};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use cw4::{Cw4QueryMsg, Member};
+use cw721_poap::msg::PoapMetadata;
+use cw_ownable::Ownership;
+use cw_storage_plus::Bound;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw-ave";
@@ -24,10 +49,15 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const NAMESPACE: &[u8] = b"aves";
 pub const CHARACTER_LIMIT: usize = 128;
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 50;
+pub const MAX_BATCH_CHECKINS: usize = 50;
+pub const MAX_BPS: u64 = 10_000;
+pub const MAX_REVENUE_SPLITS: usize = 20;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
@@ -42,35 +72,202 @@ pub fn instantiate(
         return Err(ContractError::BadEventTitleOrDescription {});
     }
 
+    let resale = msg.resale.unwrap_or_default();
+    if resale.royalty_bps > MAX_BPS {
+        return Err(ContractError::InvalidResaleSettings {});
+    }
+
     // validate guest details
     for dt in msg.guest_details {
         // ensure no duplicate guest weights
-        match GUEST_DETAILS.may_load(deps.storage, dt.guest_weight)? {
-            Some(_) => return Err(ContractError::DuplicateGuestWeight {}),
-            None => {
-                if dt.guest_type.len() > CHARACTER_LIMIT
-                    || dt.max_ticket_limit > dt.total_ticket_limit
-                {
-                    return Err(ContractError::BadGuestDetailParams {});
-                }
+        if GUEST_DETAILS.has(deps.storage, dt.guest_weight) {
+            return Err(ContractError::DuplicateGuestWeight {});
+        }
+        validate_guest_details(deps.branch(), &dt)?;
+        GUEST_DETAILS.save(deps.storage, dt.guest_weight, &dt)?;
+        TOTAL_RESERVED_BY_GUEST_TYPE.save(deps.storage, dt.guest_weight, &0)?;
+    }
 
-                // cannot set duplicate accepted tokens
-                let mut unique = Vec::new();
-                for fee in &dt.ticket_cost {
-                    if unique.contains(&fee.denom) {
-                        return Err(ContractError::DuplicateFeeDenom {});
-                    }
-                    unique.push(fee.denom.to_string());
-                }
+    // validate event stages
+    validate_event_timeline(&msg.event_timeline)?;
+    for (i, event) in msg.event_timeline.iter().enumerate() {
+        EVENT_STAGES.save(deps.storage, i as u64, event)?;
+    }
+
+    for item in GUEST_DETAILS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, gd) = item?;
+        ensure_segment_access_exists(deps.storage, &gd)?;
+    }
+    let revenue_splits = validate_revenue_splits(deps.as_ref(), msg.revenue_splits)?;
+
+    // setup cw420 groups, or use existing cw4 groups
+    if msg.existing_usher_group.is_some() && !msg.usher_admins.is_empty() {
+        return Err(ContractError::InvalidEventGroup {});
+    }
+    let mut msgs = vec![];
+    let (event_usher_contract, usher_group) = match msg.existing_usher_group {
+        Some(group) => validate_existing_group(deps.as_ref(), &env, &group)?,
+        None => {
+            let (addr, usher_msg) = instantiate_cw420_group(
+                deps.as_ref(),
+                &env,
+                msg.cw420,
+                msg.usher_admins,
+                "cw-ave-ushers",
+                0,
+            )?;
+            msgs.push(usher_msg);
+            (addr, GroupMode::Instantiated)
+        }
+    };
+    let (event_guest_contract, guest_group) = match msg.existing_guest_group {
+        Some(group) => validate_existing_group(deps.as_ref(), &env, &group)?,
+        None => {
+            let (addr, guest_msg) = instantiate_cw420_group(
+                deps.as_ref(),
+                &env,
+                msg.cw420,
+                vec![],
+                "cw-ave-guests",
+                1,
+            )?;
+            msgs.push(guest_msg);
+            (addr, GroupMode::Instantiated)
+        }
+    };
+    let poap_collection = match msg.poap {
+        Some(poap) => {
+            let (salt, addr) = instantiate2_salt(deps.as_ref(), &env, poap.cw721, 2)?;
+            msgs.push(WasmMsg::Instantiate2 {
+                admin: Some(env.contract.address.to_string()),
+                code_id: poap.cw721,
+                msg: to_json_binary(&cw721_poap::msg::InstantiateMsg {
+                    name: msg.title.clone(),
+                    symbol: "POAP".to_string(),
+                    minter: env.contract.address.to_string(),
+                    transferable: poap.transferable,
+                })?,
+                funds: vec![],
+                label: "cw-ave-poap".to_string(),
+                salt,
+            });
+            Some(addr)
+        }
+        None => None,
+    };
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            title: msg.title,
+            description: msg.description,
+            curator,
+            event_usher_contract,
+            event_guest_contract,
+            resale,
+            usher_group,
+            guest_group,
+            supervisor_weight: msg.usher_supervisor_weight,
+            poap_collection,
+            lifecycle: match msg.draft {
+                true => LifecycleState::Draft,
+                false => LifecycleState::Published,
+            },
+            factory: Some(info.sender),
+            revenue_splits,
+            webauthn_rp_id: msg.webauthn_rp_id,
+        },
+    )?;
+
+    Ok(Response::new().add_messages(msgs))
+}
+
+/// validates the label, ticket limits & prices of a guest type, registering its cw20 payment tokens.
+fn validate_guest_details(deps: DepsMut, dt: &GuestDetails) -> Result<(), ContractError> {
+    if dt.guest_type.len() > CHARACTER_LIMIT || dt.max_ticket_limit > dt.total_ticket_limit {
+        return Err(ContractError::BadGuestDetailParams {});
+    }
+
+    // cannot set duplicate accepted tokens
+    let mut unique = Vec::new();
+    for fee in &dt.ticket_cost {
+        if unique.contains(&fee.denom) {
+            return Err(ContractError::DuplicateFeeDenom {});
+        }
+        unique.push(fee.denom.to_string());
+    }
+    for fee in &dt.cw20_ticket_cost {
+        // cw20 prices must be set for cw20 token contracts
+        let token = deps.api.addr_validate(&fee.address)?;
+        let _: TokenInfoResponse = deps
+            .querier
+            .query_wasm_smart(&token, &Cw20QueryMsg::TokenInfo {})?;
+        if unique.contains(&fee.address) {
+            return Err(ContractError::DuplicateFeeDenom {});
+        }
+        unique.push(fee.address.to_string());
+        CW20_PAYMENT_TOKENS.save(deps.storage, token.as_str(), &true)?;
+    }
+    Ok(())
+}
+
+/// guest types may only grant access to existing event stages, as escrow is released by them
+fn ensure_segment_access_exists(
+    storage: &dyn Storage,
+    gd: &GuestDetails,
+) -> Result<(), ContractError> {
+    let ids = gd.event_segment_access.segment_ids();
+    if ids.is_empty() || ids.iter().any(|id| !EVENT_STAGES.has(storage, *id)) {
+        return Err(ContractError::IncorrectEventSegmentId {});
+    }
+    Ok(())
+}
 
-                GUEST_DETAILS.save(deps.storage, dt.guest_weight, &dt)?;
-                TOTAL_RESERVED_BY_GUEST_TYPE.save(deps.storage, dt.guest_weight, &0)?;
+/// validates the payees & shares of revenue splits, against the guest types of the event.
+fn validate_revenue_splits(
+    deps: Deps,
+    splits: Vec<RevenueSplit>,
+) -> Result<Vec<RevenueSplit>, ContractError> {
+    if splits.len() > MAX_REVENUE_SPLITS {
+        return Err(ContractError::InvalidRevenueSplits {});
+    }
+    let mut total_bps = 0u64;
+    for split in &splits {
+        deps.api.addr_validate(split.payee.as_str())?;
+        match &split.share {
+            RevenueShare::Bps { bps } => {
+                if *bps == 0 {
+                    return Err(ContractError::InvalidRevenueSplits {});
+                }
+                total_bps += bps;
+            }
+            RevenueShare::PerTicket {
+                guest_weight,
+                amount,
+            } => {
+                let accepted = GUEST_DETAILS
+                    .may_load(deps.storage, *guest_weight)?
+                    .ok_or(ContractError::InvalidRevenueSplits {})?
+                    .payment_options();
+                if amount.is_empty()
+                    || amount
+                        .iter()
+                        .any(|c| c.amount.is_zero() || !accepted.iter().any(|a| a.denom == c.denom))
+                {
+                    return Err(ContractError::InvalidRevenueSplits {});
+                }
             }
         }
     }
+    if total_bps > MAX_BPS {
+        return Err(ContractError::InvalidRevenueSplits {});
+    }
+    Ok(splits)
+}
 
-    // validate event stages
-    for (i, event) in msg.event_timeline.iter().enumerate() {
+/// validates the event stages, in the order of their ids.
+fn validate_event_timeline(timeline: &[EventSegment]) -> Result<(), ContractError> {
+    for (i, event) in timeline.iter().enumerate() {
         // Validate that start date is before or equal to end date
         if event.start > event.end {
             return Err(ContractError::InvalidEventDates {});
@@ -78,84 +275,87 @@ pub fn instantiate(
         if event.stage_description.len() > 128usize {
             return Err(ContractError::BadEventDescriptionLength {});
         }
+        if event.escrow_weight == Some(0) {
+            return Err(ContractError::InvalidEscrowWeight {});
+        }
 
         // For events that are not the first, check that the previous end date is before or at the next start date
         if i > 0 {
-            let prev_event = &msg.event_timeline[i - 1];
+            let prev_event = &timeline[i - 1];
             if prev_event.end > event.start {
                 return Err(ContractError::OverlappingEventDates {});
             }
         }
-
-        // Save the event stage
-        EVENT_STAGES.save(deps.storage, i as u64, event)?;
     }
+    Ok(())
+}
 
-    // setup cw420 groups
-    let cw721_checksum = deps.querier.query_wasm_code_info(msg.cw420)?;
-    let usher_salt = generate_instantiate_salt2(&cw721_checksum.checksum, NAMESPACE);
-    let mut guest_salt_data = usher_salt.to_vec();
-    guest_salt_data[0] ^= 1; // Flip the first byte to ensure it's different
-    let guest_salt = Binary::new(guest_salt_data);
+/// salt & address of a contract this contract instantiates with `Instantiate2`.
+/// contracts share the salt derived from the checksum of their code, with the first byte flipped by `flip` to tell them apart.
+fn instantiate2_salt(
+    deps: Deps,
+    env: &Env,
+    code_id: u64,
+    flip: u8,
+) -> Result<(Binary, Addr), ContractError> {
+    let cw721_checksum = deps.querier.query_wasm_code_info(code_id)?;
+    let mut salt_data = generate_instantiate_salt2(&cw721_checksum.checksum, NAMESPACE).to_vec();
+    salt_data[0] ^= flip;
+    let salt = Binary::new(salt_data);
 
     let contract_address = deps.api.addr_canonicalize(env.contract.address.as_str())?;
-
-    let usher_cw420 = match instantiate2_address(
-        cw721_checksum.checksum.as_slice(),
-        &contract_address,
-        usher_salt.as_slice(),
-    ) {
-        Ok(addr) => addr,
-        Err(err) => return Err(ContractError::from(err)),
-    };
-
-    let guest_cw420 = match instantiate2_address(
+    let addr = instantiate2_address(
         cw721_checksum.checksum.as_slice(),
         &contract_address,
-        guest_salt.as_slice(),
-    ) {
-        Ok(addr) => addr,
-        Err(err) => return Err(ContractError::from(err)),
-    };
-
-    let usher_msg = WasmMsg::Instantiate2 {
-        admin: Some(env.contract.address.to_string()),
-        code_id: msg.cw420,
-        msg: to_json_binary(&cw420::msg::InstantiateMsg {
-            admin: Some(env.contract.address.to_string()),
-            members: msg.usher_admins,
-        })?,
-        funds: vec![],
-        label: "cw-ave-ushers".to_string(),
-        salt: usher_salt,
-    };
+        salt.as_slice(),
+    )?;
+    Ok((salt, deps.api.addr_humanize(&addr)?))
+}
 
-    let guest_msg = WasmMsg::Instantiate2 {
+/// forms the message instantiating a cw420 group administered by this contract, returning its address.
+/// `flip` tells the usher & guest group salts apart, the label is only displayed.
+fn instantiate_cw420_group(
+    deps: Deps,
+    env: &Env,
+    code_id: u64,
+    members: Vec<Member>,
+    label: &str,
+    flip: u8,
+) -> Result<(Addr, WasmMsg), ContractError> {
+    let (salt, group) = instantiate2_salt(deps, env, code_id, flip)?;
+
+    let msg = WasmMsg::Instantiate2 {
         admin: Some(env.contract.address.to_string()),
-        code_id: msg.cw420,
+        code_id,
         msg: to_json_binary(&cw420::msg::InstantiateMsg {
             admin: Some(env.contract.address.to_string()),
-            members: vec![],
+            members,
         })?,
         funds: vec![],
-        label: "cw-ave-guests".to_string(),
-        salt: guest_salt,
+        label: label.to_string(),
+        salt,
     };
-    let event_usher_contract = deps.api.addr_humanize(&usher_cw420)?;
-    let event_guest_contract = deps.api.addr_humanize(&guest_cw420)?;
-
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            title: msg.title,
-            description: msg.description,
-            curator,
-            event_usher_contract,
-            event_guest_contract,
-        },
-    )?;
+    Ok((group, msg))
+}
 
-    Ok(Response::new().add_messages(vec![usher_msg, guest_msg]))
+/// validates an existing cw4 group by querying it.
+/// the event can only update its members if this contract is the group admin.
+fn validate_existing_group(
+    deps: Deps,
+    env: &Env,
+    group: &str,
+) -> Result<(Addr, GroupMode), ContractError> {
+    let group = deps.api.addr_validate(group)?;
+    let _: cw4::TotalWeightResponse = deps
+        .querier
+        .query_wasm_smart(&group, &Cw4QueryMsg::TotalWeight { at_height: None })
+        .map_err(|_| ContractError::InvalidEventGroup {})?;
+    let admin: cw4::AdminResponse = deps
+        .querier
+        .query_wasm_smart(&group, &Cw4QueryMsg::Admin {})
+        .map_err(|_| ContractError::InvalidEventGroup {})?;
+    let writable = admin.admin.as_deref() == Some(env.contract.address.as_str());
+    Ok((group, GroupMode::Existing { writable }))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -166,12 +366,113 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::PurchaseTickets { guests } => perform_ticket_purchase(deps, info, guests),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::PurchaseTickets { guests } => {
+            perform_ticket_purchase(deps, env, info.sender, info.funds, guests)
+        }
         ExecuteMsg::RefundUnconfirmedTickets { guests } => {
-            refund_unconfirmed_ticket_purchase(deps, info, guests)
+            refund_unconfirmed_ticket_purchase(deps, env, info, guests)
+        }
+        ExecuteMsg::CheckInGuest { checkin } => perform_checkin_guest(deps, env, info, checkin),
+        ExecuteMsg::BatchCheckInGuests { checkins, mode } => {
+            perform_batch_checkin_guests(deps, env, info, checkins, mode)
+        }
+        ExecuteMsg::UpdateEvent {
+            title,
+            description,
+            add_guest_types,
+            update_guest_types,
+            reschedule_segments,
+        } => perform_update_event(
+            deps,
+            env,
+            info,
+            title,
+            description,
+            add_guest_types,
+            update_guest_types,
+            reschedule_segments,
+        ),
+        ExecuteMsg::UpdateCheckinGracePeriods {
+            segment_id,
+            early_checkin_grace,
+            late_checkin_grace,
+        } => perform_update_checkin_grace_periods(
+            deps,
+            info,
+            segment_id,
+            early_checkin_grace,
+            late_checkin_grace,
+        ),
+        ExecuteMsg::SetHomieCheckinSecret {
+            ticket_addr,
+            secret_hash,
+        } => perform_set_homie_checkin_secret(deps, info, ticket_addr, secret_hash),
+        ExecuteMsg::RevokeHomieCheckinSecret { ticket_addr } => {
+            perform_revoke_homie_checkin_secret(deps, info, ticket_addr)
+        }
+        ExecuteMsg::CheckInWithHomieSecret {
+            ticket_addr,
+            secret,
+            event_segment_ids,
+            gate,
+        } => perform_checkin_with_homie_secret(
+            deps,
+            env,
+            info,
+            ticket_addr,
+            secret,
+            event_segment_ids,
+            gate,
+        ),
+        ExecuteMsg::CheckOutGuest {
+            ticket_addr,
+            event_segment_ids,
+            gate,
+        } => perform_checkout_guest(deps, env, info, ticket_addr, event_segment_ids, gate),
+        ExecuteMsg::CheckOut { event_segment_ids } => {
+            perform_checkout(deps, env, info, event_segment_ids)
+        }
+        ExecuteMsg::ListTicketForResale { ticket_addr, price } => {
+            perform_list_ticket_for_resale(deps, env, info, ticket_addr, price)
+        }
+        ExecuteMsg::CancelResaleListing { ticket_addr } => {
+            perform_cancel_resale_listing(deps, info, ticket_addr)
+        }
+        ExecuteMsg::BuyResaleTicket {
+            ticket_addr,
+            new_ticket_addr,
+        } => perform_buy_resale_ticket(
+            deps,
+            env,
+            info.sender,
+            info.funds,
+            ticket_addr,
+            new_ticket_addr,
+        ),
+        ExecuteMsg::WithdrawFromWaitlist { tickets } => {
+            perform_withdraw_from_waitlist(deps, info, tickets)
+        }
+        ExecuteMsg::AddUshers { ushers } => perform_update_ushers(deps, info, ushers, vec![]),
+        ExecuteMsg::RemoveUshers { ushers } => perform_update_ushers(deps, info, vec![], ushers),
+        ExecuteMsg::UpdateUsherWeights { ushers } => {
+            perform_update_usher_weights(deps, info, ushers)
+        }
+        ExecuteMsg::SetUsherScope { usher, scope } => {
+            perform_set_usher_scope(deps, info, usher, scope)
         }
-        ExecuteMsg::CheckInGuest { checkin } => perform_checkin_guest(deps, info, checkin),
         ExecuteMsg::ClaimTicketPayments {} => perform_claim_ticket_payments(deps, env, info),
+        ExecuteMsg::OpenSales {} => perform_open_sales(deps, env, info),
+        ExecuteMsg::CancelEvent { segment_ids } => {
+            perform_cancel_event(deps, env, info, segment_ids)
+        }
+        ExecuteMsg::ClaimCancellationRefunds { tickets } => {
+            perform_claim_cancellation_refunds(deps, env, info, tickets)
+        }
+        ExecuteMsg::WithdrawRevenueShare { denom } => {
+            perform_withdraw_revenue_share(deps, env, info, denom)
+        }
+        ExecuteMsg::SettleEvent {} => perform_settle_event(deps, env, info),
         ExecuteMsg::ClaimTicketReservedByHomie { homie_addr } => {
             perform_claim_ticket_reserved_by_homie(deps, info, homie_addr)
         }
@@ -183,7 +484,7 @@ pub fn execute(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::EventSegments {} => {
@@ -198,24 +499,105 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             guest,
             event_stage_id,
         } => to_json_binary(
-            &ATTENDANCE_RECORD
+            &attendance_records()
                 .may_load(deps.storage, (&guest, event_stage_id))?
-                .unwrap_or_default(),
+                .is_some_and(|r| r.checked_in),
         ),
         QueryMsg::GuestAttendanceStatusAll { guest } => {
             let mut attendance_status = Vec::new();
-            let prefix = ATTENDANCE_RECORD.prefix(&guest);
+            let prefix = attendance_records().prefix(&guest);
             for item in prefix.range(deps.storage, None, None, cosmwasm_std::Order::Ascending) {
-                let (event_stage_id, status) = item?;
-                attendance_status.push((event_stage_id, status));
+                let (event_stage_id, record) = item?;
+                attendance_status.push((event_stage_id, record.checked_in));
             }
             to_json_binary(&attendance_status)
         }
+        QueryMsg::GuestAttendanceRecords { guest } => to_json_binary(
+            &attendance_records()
+                .prefix(&guest)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, record)| record))
+                .collect::<StdResult<Vec<AttendanceRecord>>>()?,
+        ),
+        QueryMsg::SegmentAttendees {
+            segment_id,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(|ticket_addr| Bound::exclusive((ticket_addr, segment_id)));
+
+            let res = attendance_records()
+                .idx
+                .segment
+                .prefix(segment_id)
+                .range(deps.storage, start, None, Order::Ascending)
+                .filter(|item| item.as_ref().map_or(true, |(_, r)| r.checked_in))
+                .take(limit)
+                .map(|item| item.map(|(_, record)| record))
+                .collect::<StdResult<Vec<AttendanceRecord>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::UsherCheckins {
+            usher,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+            let usher = deps.api.addr_validate(&usher)?;
+
+            let res = checkin_log()
+                .idx
+                .usher
+                .prefix(usher.to_string())
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, entry)| entry))
+                .collect::<StdResult<Vec<CheckInLogEntry>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::GuestCheckins {
+            guest,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+
+            let res = checkin_log()
+                .sub_prefix(&guest)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, entry)| entry))
+                .collect::<StdResult<Vec<CheckInLogEntry>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::Occupancy {} => {
+            let res = EVENT_STAGES
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| {
+                    let (segment_id, segment) = item?;
+                    Ok(OccupancyRes {
+                        segment_id,
+                        occupancy: segment_occupancy(
+                            deps.storage,
+                            &segment,
+                            segment_id,
+                            env.block.time,
+                        )?,
+                        capacity: segment.capacity,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&res)
+        }
         QueryMsg::TicketPaymentOptionsByGuestWeight { guest_weight } => {
             let gd = GUEST_DETAILS.load(deps.storage, guest_weight)?;
             Ok(to_json_binary(&TicketPaymentOption {
                 guest_type: gd.guest_type,
                 payment_options: gd.ticket_cost,
+                cw20_payment_options: gd.cw20_ticket_cost,
             })?)
         }
         QueryMsg::AllTicketPaymentOptions {} => Ok(to_json_binary(
@@ -225,6 +607,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     res.map(|(_, guest_details)| TicketPaymentOption {
                         guest_type: guest_details.guest_type,
                         payment_options: guest_details.ticket_cost,
+                        cw20_payment_options: guest_details.cw20_ticket_cost,
                     })
                 })
                 .collect::<StdResult<Vec<TicketPaymentOption>>>()?,
@@ -238,10 +621,184 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .map(|res| res.map(|(_, guest_details)| guest_details))
                 .collect::<StdResult<Vec<GuestDetails>>>()?,
         )?),
-        // QueryMsg::GuestTicketsByReservedWeight { guest } => RESERVED_TICKETS.load(store, k),
+        QueryMsg::TicketReceipt { ticket_addr } => {
+            to_json_binary(&ticket_receipts().load(deps.storage, &ticket_addr)?)
+        }
+        QueryMsg::TicketReceiptsByPurchaser {
+            purchaser,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::<String>::exclusive);
+            let purchaser = deps.api.addr_validate(&purchaser)?;
+
+            let res = ticket_receipts()
+                .idx
+                .purchaser
+                .prefix(purchaser.to_string())
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(ticket_addr, receipt)| TicketReceiptRes {
+                        ticket_addr,
+                        receipt,
+                    })
+                })
+                .collect::<StdResult<Vec<TicketReceiptRes>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::TicketReceiptsByGuestType {
+            guest_weight,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::<String>::exclusive);
+
+            let res = ticket_receipts()
+                .idx
+                .guest_weight
+                .prefix(guest_weight)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(ticket_addr, receipt)| TicketReceiptRes {
+                        ticket_addr,
+                        receipt,
+                    })
+                })
+                .collect::<StdResult<Vec<TicketReceiptRes>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::EscrowStatus {} => to_json_binary(&escrow_status(deps.storage, env.block.time)?),
+        QueryMsg::Status {} => to_json_binary(&event_status(deps.storage, env.block.time)?),
+        QueryMsg::PayeeRevenue { payee } => {
+            let payee = deps.api.addr_validate(&payee)?;
+            let splits = CONFIG.load(deps.storage)?.revenue_splits;
+            let revenue = payee_revenue(deps.storage, &splits, env.block.time)?
+                .into_iter()
+                .find(|p| p.payee == payee)
+                .ok_or(StdError::not_found("revenue split payee"))?;
+            to_json_binary(&revenue)
+        }
+        QueryMsg::AllPayeeRevenue {} => {
+            let splits = CONFIG.load(deps.storage)?.revenue_splits;
+            to_json_binary(&payee_revenue(deps.storage, &splits, env.block.time)?)
+        }
+        QueryMsg::HomieCheckinSecret { ticket_addr } => {
+            to_json_binary(&HOMIE_CHECKIN_SECRETS.may_load(deps.storage, &ticket_addr)?)
+        }
+        QueryMsg::ResaleListing { ticket_addr } => {
+            to_json_binary(&RESALE_LISTINGS.load(deps.storage, &ticket_addr)?)
+        }
+        QueryMsg::ResaleListings { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let res = RESALE_LISTINGS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(ticket_addr, listing)| ResaleListingRes {
+                        ticket_addr,
+                        listing,
+                    })
+                })
+                .collect::<StdResult<Vec<ResaleListingRes>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::TicketAllowance { purchaser } => {
+            let purchaser = deps.api.addr_validate(&purchaser)?;
+            let res = GUEST_DETAILS
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| {
+                    let (guest_weight, gd) = item?;
+                    let reserved = RESERVED_TICKETS
+                        .may_load(deps.storage, (&purchaser, guest_weight))?
+                        .unwrap_or_default();
+                    Ok(TicketAllowanceRes {
+                        guest_weight,
+                        guest_type: gd.guest_type,
+                        reserved,
+                        remaining: match gd.max_ticket_limit {
+                            0 => None,
+                            limit => Some(limit.saturating_sub(reserved)),
+                        },
+                    })
+                })
+                .collect::<StdResult<Vec<TicketAllowanceRes>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::Waitlist {
+            guest_weight,
+            start_after,
+            limit,
+        } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start = start_after.map(Bound::exclusive);
+
+            let res = WAITLIST
+                .prefix(guest_weight)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|item| {
+                    let (position, entry) = item?;
+                    waitlist_entry_res(deps.storage, guest_weight, position, entry)
+                })
+                .collect::<StdResult<Vec<WaitlistEntryRes>>>()?;
+            to_json_binary(&res)
+        }
+        QueryMsg::UsherScope { usher } => {
+            to_json_binary(&USHER_SCOPES.may_load(deps.storage, &deps.api.addr_validate(&usher)?)?)
+        }
+        QueryMsg::Ushers { start_after, limit } => {
+            let cfg = CONFIG.load(deps.storage)?;
+            let res: cw4::MemberListResponse = deps.querier.query_wasm_smart(
+                &cfg.event_usher_contract,
+                &Cw4QueryMsg::ListMembers {
+                    start_after,
+                    limit: Some(limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)),
+                },
+            )?;
+            to_json_binary(&res.members)
+        }
+        QueryMsg::WaitlistEntry { ticket_addr } => {
+            let (guest_weight, position) = WAITLISTED_TICKETS.load(deps.storage, &ticket_addr)?;
+            let entry = WAITLIST.load(deps.storage, (guest_weight, position))?;
+            to_json_binary(&waitlist_entry_res(
+                deps.storage,
+                guest_weight,
+                position,
+                entry,
+            )?)
+        }
     }
 }
 
+/// a waitlist entry, with the number of entries ahead of it for its guest type
+fn waitlist_entry_res(
+    storage: &dyn Storage,
+    guest_weight: u64,
+    position: u64,
+    entry: WaitlistEntry,
+) -> StdResult<WaitlistEntryRes> {
+    let ahead = WAITLIST
+        .prefix(guest_weight)
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(position)),
+            Order::Ascending,
+        )
+        .count() as u32;
+    Ok(WaitlistEntryRes {
+        position,
+        ahead,
+        entry,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
     Ok(Response::new())
@@ -250,15 +807,23 @@ pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Respons
 /// Entry point to purchase event tickets
 pub fn perform_ticket_purchase(
     deps: DepsMut,
-    info: MessageInfo,
+    env: Env,
+    purchaser: Addr,
+    funds: Vec<Coin>,
     guests: Vec<RegisteringGuest>,
 ) -> Result<Response, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
-    let mut msgs = Vec::new();
+    ensure_phase(deps.storage, env.block.time, SALE_PHASES)?;
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut res = Response::new().add_attribute("action", "purchase_tickets");
+    // funds are spent down across every guest type being purchased
+    let mut remaining_funds = funds;
+    let mut dev_fees: Vec<Coin> = Vec::new();
 
     for guest in guests {
         // check if guest type exists
         let gd = GUEST_DETAILS.load(deps.storage, guest.guest_weight)?;
+        ensure_segments_not_cancelled(deps.storage, &gd)?;
         let count = TOTAL_RESERVED_BY_GUEST_TYPE.load(deps.storage, gd.guest_weight)?;
         let to_reserve = guest.reap.len();
         // Calculate how many tickets we can actually process (respecting the limit)
@@ -267,168 +832,1574 @@ pub fn perform_ticket_purchase(
 
         // Split the guest list - prioritize first entries in the array
         let to_process = &guest.reap[..process_count];
-        // todo: implmeent overbooking feature where we can still accept these payments if neccessary
-        // let overflow = &guest.reap[process_count..];
-        // if !overflow.is_empty() {
-        // }
+        // entries past the ticket limit join the waitlist while it has room, the rest are rejected
+        let overflow = &guest.reap[process_count..];
+        let waitlisted_count = WAITLIST_COUNT
+            .may_load(deps.storage, gd.guest_weight)?
+            .unwrap_or_default();
+        let waitlist_room = gd.overbooking_limit.saturating_sub(waitlisted_count) as usize;
+        let (to_waitlist, sold_out) = overflow.split_at(overflow.len().min(waitlist_room));
+        for sold_out in sold_out {
+            res = res.add_attribute("sold_out", &sold_out.ticket_addr);
+        }
+
+        // guest groups the event cannot update must already list tickets with their guest type
+        if !cfg.guest_group.writable() {
+            for ticket in to_process.iter().chain(to_waitlist) {
+                let weight = check_if_cw420_member(
+                    deps.as_ref(),
+                    &cfg.event_guest_contract,
+                    &deps.api.addr_validate(&ticket.ticket_addr)?,
+                )?;
+                if weight != Some(gd.guest_weight) {
+                    return Err(ContractError::NotInGuestGroup {});
+                }
+            }
+        }
 
         // if tickets being reserved are greater than limit for this guest type, strip the # of entries in the tickets being reserved from the object so that we will reach the limit and not error.
-        let (reserved, remaining_funds, dev_fee_msg) = count_tickets_and_remainder(
-            LICENSE_ADDR.load(deps.storage)?.to_string(),
-            &info.funds,
-            gd.ticket_cost,
+        let (reserved, leftover_funds, receipts, dev_fee_coins) = count_tickets_and_remainder(
+            &purchaser,
+            gd.guest_weight,
+            &env,
+            &remaining_funds,
+            gd.payment_options(),
             to_process,
         );
+        remaining_funds = leftover_funds;
+        for fee in dev_fee_coins {
+            add_to_coins(&mut dev_fees, fee);
+        }
 
-        // return any overflow funds sent.
-        msgs.extend([
-            form_return_payment_overflow_msgs(&remaining_funds, &info.sender),
-            dev_fee_msg,
-        ]);
-
-        RESERVED_TICKETS.update(deps.storage, &gd.guest_weight, |a| match a {
-            Some(mut td) => {
-                td += to_reserve as u128;
-                if td > gd.max_ticket_limit.into() {
-                    return Err(ContractError::CannotReserveTicketCount {});
-                }
-                Ok::<u128, ContractError>(td)
-            }
-            None => {
-                if reserved <= gd.max_ticket_limit.into() {
-                    Ok(reserved)
-                } else {
-                    Err(ContractError::CannotReserveTicketCount {})
-                }
-            }
-        })?;
+        for (ticket_addr, receipt) in &receipts {
+            issue_ticket(deps.storage, &gd, ticket_addr, receipt)?;
+            res = res.add_attributes(vec![
+                attr("ticket_addr", ticket_addr),
+                attr("guest_weight", gd.guest_weight.to_string()),
+                attr(
+                    "paid",
+                    coin(receipt.price.u128(), &receipt.denom).to_string(),
+                ),
+            ]);
+        }
+        for unpaid in to_process
+            .iter()
+            .filter(|r| !receipts.iter().any(|(paid, _)| paid == &r.ticket_addr))
+        {
+            res = res.add_attribute("unpaid", &unpaid.ticket_addr);
+        }
 
-        msgs.push(
-            form_update_guestlist_msg(
-                &info.sender,
+        // waitlisted payments are held in full, the dev fee is only taken once promoted
+        let (waitlisted, leftover_funds, entries, _) = count_tickets_and_remainder(
+            &purchaser,
+            gd.guest_weight,
+            &env,
+            &remaining_funds,
+            gd.payment_options(),
+            to_waitlist,
+        );
+        remaining_funds = leftover_funds;
+        for (ticket_addr, receipt) in entries.iter() {
+            let position = join_waitlist(deps.storage, ticket_addr, receipt)?;
+            res = res.add_attributes(vec![
+                attr("waitlisted", ticket_addr),
+                attr("waitlist_position", position.to_string()),
+            ]);
+        }
+        for unpaid in to_waitlist
+            .iter()
+            .filter(|r| !entries.iter().any(|(paid, _)| paid == &r.ticket_addr))
+        {
+            res = res.add_attribute("unpaid", &unpaid.ticket_addr);
+        }
+        if waitlisted > 0 {
+            WAITLIST_COUNT.save(
+                deps.storage,
+                gd.guest_weight,
+                &(waitlisted_count + waitlisted as u32),
+            )?;
+        }
+        TOTAL_RESERVED_BY_GUEST_TYPE.save(
+            deps.storage,
+            gd.guest_weight,
+            &(count + reserved as u32),
+        )?;
+
+        // tickets & waitlisted purchases, including those for homies, count against the purchaser
+        reserve_tickets(
+            deps.storage,
+            &purchaser,
+            &gd,
+            (reserved + waitlisted) as u32,
+        )?;
+
+        // only tickets that were paid for join the guest list
+        if !receipts.is_empty() {
+            let paid: Vec<String> = receipts.into_iter().map(|(addr, _)| addr).collect();
+            let guestlist_msg = form_update_guestlist_msg(
+                &purchaser,
                 deps.storage,
-                &guest.reap,
+                &paid,
                 gd.guest_weight,
                 &cfg.event_guest_contract,
-            )?
-            .into(),
-        );
+            )?;
+            if cfg.guest_group.writable() {
+                msgs.push(guestlist_msg.into());
+            }
+        }
     }
-    Ok(Response::new())
-}
 
-/// Entry point to checkin guests as event usher
-pub fn perform_checkin_guest(
-    deps: DepsMut,
-    info: MessageInfo,
-    checkin: CheckInDetails,
-) -> Result<Response, ContractError> {
-    // sender must be one of event ushers
-    let cfg = CONFIG.load(deps.storage)?;
-    if check_if_cw420_member(deps.as_ref(), &cfg.event_usher_contract, &info.sender)?.is_none() {
-        return Err(ContractError::NotAnEventUsher {});
-    };
+    // return any overflow funds sent.
+    msgs.extend(form_payment_msgs(
+        deps.storage,
+        &purchaser,
+        remaining_funds,
+    )?);
+    msgs.extend(form_payment_msgs(
+        deps.storage,
+        &LICENSE_ADDR.load(deps.storage)?,
+        dev_fees,
+    )?);
 
-    // verify signature came from guest and is valid
-    if !deps.api.secp256k1_verify(
-        &sha256(preamble_msg_arb_036(&checkin.ticket_addr, &checkin.signed_data).as_bytes()),
-        &checkin.signature,
-        &checkin.pubkey,
-    )? {
-        return Err(ContractError::CheckinVerificationFailed {});
-    };
+    Ok(res.add_messages(msgs))
+}
 
-    // parse signed_data to retrieve specific guest weight
-    let signature_data: CheckInSignatureData = from_json(checkin.signed_data)?;
+/// errors if event segments a guest type grants access to were cancelled, as its tickets are no longer sold.
+fn ensure_segments_not_cancelled(
+    storage: &dyn Storage,
+    gd: &GuestDetails,
+) -> Result<(), ContractError> {
+    let (cancelled, _) = cancelled_escrow_weights(storage, &gd.event_segment_access)?;
+    if cancelled > 0 {
+        return Err(ContractError::GuestTypeSegmentsCancelled {
+            guest_weight: gd.guest_weight,
+        });
+    }
+    Ok(())
+}
 
-    if let Some(guest_weight) = check_if_cw420_member(
-        deps.as_ref(),
-        &cfg.event_guest_contract,
-        &deps.api.addr_validate(&checkin.ticket_addr)?,
-    )? {
-        let guest_details = GUEST_DETAILS.load(deps.storage, guest_weight)?;
-
-        // if giving ticket to another external wallet,
-        // create composite storage key to prevent unauthorized checkin for another wallet address
-
-        // consume ticket
-        // RESERVED_TICKETS.update(deps.storage, &checkin.ticket_addr, |a| match a {
-        //     Some(mut td) => {
-        //         if td == 0u128 {
-        //             return Err(ContractError::GuestAlreadyCheckedIn {});
-        //         }
-        //         td -= 1;
-        //         Ok::<u128, ContractError>(td)
-        //     }
-        //     None => Err(ContractError::NoReservedTicketsForGuest {}),
-        // })?;
-
-        // recurisvely update guest status for specific segment
-        match guest_details.event_segment_access {
-            crate::state::EventSegmentAccessType::SingleSegment { id } => {
-                if !signature_data.event_segment_ids.contains(&id) {
-                    return Err(ContractError::IncorrectEventSegmentId {});
-                }
-                update_attendance_record(deps.storage, &checkin.ticket_addr, id)?;
-            }
-            crate::state::EventSegmentAccessType::AnyOfSpecificSegments { ids } => {
-                for checkin_id in signature_data.event_segment_ids {
-                    if !ids.contains(&checkin_id) {
-                        return Err(ContractError::IncorrectEventSegmentId {});
-                    }
-                    update_attendance_record(deps.storage, &checkin.ticket_addr, checkin_id)?;
-                }
-            }
-            crate::state::EventSegmentAccessType::AllOfSpecificSegments { ids } => {
-                // we just checkin all automatically
-                for checkin_id in ids {
-                    update_attendance_record(deps.storage, &checkin.ticket_addr, checkin_id)?;
-                }
-            }
+/// records a paid ticket: escrowing its revenue, saving its receipt & creating its attendance records.
+fn issue_ticket(
+    storage: &mut dyn Storage,
+    gd: &GuestDetails,
+    ticket_addr: &String,
+    receipt: &TicketReceipt,
+) -> Result<(), ContractError> {
+    if ticket_receipts().has(storage, ticket_addr) || WAITLISTED_TICKETS.has(storage, ticket_addr) {
+        return Err(ContractError::TicketAlreadyPurchased {});
+    }
+    // ticket revenue stays in escrow until the event segments of this guest type end
+    escrow_ticket_revenue(
+        storage,
+        gd,
+        &coin((receipt.price - receipt.dev_fee).u128(), &receipt.denom),
+    )?;
+    ticket_receipts().save(storage, ticket_addr, receipt)?;
+    TICKETS_SOLD.update(storage, (gd.guest_weight, &receipt.denom), |count| {
+        Ok::<u32, StdError>(count.unwrap_or_default() + 1)
+    })?;
+    // tickets can checkin to each segment their guest type has access to
+    for id in gd.event_segment_access.segment_ids() {
+        attendance_records().save(
+            storage,
+            (ticket_addr, id),
+            &AttendanceRecord {
+                ticket_addr: ticket_addr.to_string(),
+                segment_id: id,
+                checked_in: false,
+                checkin: None,
+                checked_out: false,
+                reentries: 0,
+                checkout: None,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// counts tickets reserved by a purchaser against the `max_ticket_limit` of their guest type.
+fn reserve_tickets(
+    storage: &mut dyn Storage,
+    purchaser: &Addr,
+    gd: &GuestDetails,
+    count: u32,
+) -> Result<(), ContractError> {
+    if count == 0 {
+        return Ok(());
+    }
+    RESERVED_TICKETS.update(storage, (purchaser, gd.guest_weight), |reserved| {
+        let reserved = reserved.unwrap_or_default() + count;
+        // a limit of 0 is unlimited
+        if gd.max_ticket_limit != 0 && reserved > gd.max_ticket_limit {
+            return Err(ContractError::CannotReserveTicketCount {});
         }
-    } else {
-        return Err(ContractError::GuestTypeIncorrect {});
-    };
+        Ok(reserved)
+    })?;
+    Ok(())
+}
 
-    Ok(Response::new())
+/// returns a refunded or withdrawn ticket to the allowance of its purchaser.
+fn release_reserved_ticket(
+    storage: &mut dyn Storage,
+    purchaser: &Addr,
+    guest_weight: u64,
+) -> StdResult<()> {
+    let reserved = RESERVED_TICKETS
+        .may_load(storage, (purchaser, guest_weight))?
+        .unwrap_or_default()
+        .saturating_sub(1);
+    match reserved {
+        0 => RESERVED_TICKETS.remove(storage, (purchaser, guest_weight)),
+        _ => RESERVED_TICKETS.save(storage, (purchaser, guest_weight), &reserved)?,
+    }
+    Ok(())
 }
 
-pub fn update_attendance_record(
+/// adds a paid purchase to the end of the waitlist of its guest type, returning its position.
+fn join_waitlist(
     storage: &mut dyn Storage,
     ticket_addr: &String,
-    event_segment_id: u64,
-) -> Result<bool, ContractError> {
-    ATTENDANCE_RECORD.update(storage, (ticket_addr, event_segment_id), |ci| {
-        if let Some(status) = ci {
-            match status {
-                true => Err(ContractError::GuestAlreadyCheckedIn {}),
-                false => Ok(true),
-            }
-        } else {
-            Err(ContractError::GuestTypeIncorrect {})
+    receipt: &TicketReceipt,
+) -> Result<u64, ContractError> {
+    if ticket_receipts().has(storage, ticket_addr) || WAITLISTED_TICKETS.has(storage, ticket_addr) {
+        return Err(ContractError::TicketAlreadyPurchased {});
+    }
+    let position = NEXT_WAITLIST_POSITION
+        .may_load(storage)?
+        .unwrap_or_default();
+    NEXT_WAITLIST_POSITION.save(storage, &(position + 1))?;
+    WAITLIST.save(
+        storage,
+        (receipt.guest_weight, position),
+        &WaitlistEntry {
+            ticket_addr: ticket_addr.to_string(),
+            receipt: receipt.clone(),
+        },
+    )?;
+    WAITLISTED_TICKETS.save(storage, ticket_addr, &(receipt.guest_weight, position))?;
+    Ok(position)
+}
+
+/// promotes waitlisted purchases of a guest type to tickets, in the order they joined,
+/// until the guest type is sold out again.
+fn promote_from_waitlist(
+    storage: &mut dyn Storage,
+    guest_weight: u64,
+    cfg: &Config,
+) -> Result<(Vec<Attribute>, Vec<CosmosMsg>), ContractError> {
+    let gd = GUEST_DETAILS.load(storage, guest_weight)?;
+    // guest types with cancelled segments are no longer sold, their waitlist can only be withdrawn from
+    if ensure_segments_not_cancelled(storage, &gd).is_err() {
+        return Ok((vec![], vec![]));
+    }
+    let count = TOTAL_RESERVED_BY_GUEST_TYPE.load(storage, guest_weight)?;
+    let open = gd.total_ticket_limit.saturating_sub(count) as usize;
+    let promoted = WAITLIST
+        .prefix(guest_weight)
+        .range(storage, None, None, Order::Ascending)
+        .take(open)
+        .collect::<StdResult<Vec<(u64, WaitlistEntry)>>>()?;
+
+    let mut attrs = vec![];
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut dev_fees = vec![];
+    for (position, entry) in &promoted {
+        WAITLIST.remove(storage, (guest_weight, *position));
+        WAITLISTED_TICKETS.remove(storage, &entry.ticket_addr);
+        let receipt = &entry.receipt;
+        issue_ticket(storage, &gd, &entry.ticket_addr, receipt)?;
+        add_to_coins(&mut dev_fees, coin(receipt.dev_fee.u128(), &receipt.denom));
+        let guestlist_msg = form_update_guestlist_msg(
+            &receipt.purchaser,
+            storage,
+            std::slice::from_ref(&entry.ticket_addr),
+            guest_weight,
+            &cfg.event_guest_contract,
+        )?;
+        if cfg.guest_group.writable() {
+            msgs.push(guestlist_msg.into());
         }
-    })
+        attrs.push(attr("promoted", &entry.ticket_addr));
+    }
+    if promoted.is_empty() {
+        return Ok((attrs, msgs));
+    }
+
+    let promoted_count = promoted.len() as u32;
+    TOTAL_RESERVED_BY_GUEST_TYPE.save(storage, guest_weight, &(count + promoted_count))?;
+    WAITLIST_COUNT.update(storage, guest_weight, |count| {
+        Ok::<u32, StdError>(count.unwrap_or_default().saturating_sub(promoted_count))
+    })?;
+    msgs.extend(form_payment_msgs(
+        storage,
+        &LICENSE_ADDR.load(storage)?,
+        dev_fees,
+    )?);
+    Ok((attrs, msgs))
 }
 
-fn refund_unconfirmed_ticket_purchase(
-    _deps: DepsMut,
-    _info: MessageInfo,
-    _guest: Vec<String>,
+/// Entry point to leave the waitlist, returning the full price paid to the purchaser.
+pub fn perform_withdraw_from_waitlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    tickets: Vec<String>,
 ) -> Result<Response, ContractError> {
-    Ok(Response::new())
+    let mut refund = vec![];
+    let mut res = Response::new().add_attribute("action", "withdraw_from_waitlist");
+
+    for ticket_addr in tickets {
+        let (guest_weight, position) = WAITLISTED_TICKETS
+            .may_load(deps.storage, &ticket_addr)?
+            .ok_or(ContractError::NotOnWaitlist {})?;
+        let entry = WAITLIST.load(deps.storage, (guest_weight, position))?;
+        if info.sender != entry.receipt.purchaser {
+            return Err(ContractError::NotTicketPurchaser {});
+        }
+
+        WAITLIST.remove(deps.storage, (guest_weight, position));
+        WAITLISTED_TICKETS.remove(deps.storage, &ticket_addr);
+        WAITLIST_COUNT.update(deps.storage, guest_weight, |count| {
+            Ok::<u32, StdError>(count.unwrap_or_default().saturating_sub(1))
+        })?;
+        release_reserved_ticket(deps.storage, &entry.receipt.purchaser, guest_weight)?;
+        add_to_coins(
+            &mut refund,
+            coin(entry.receipt.price.u128(), entry.receipt.denom),
+        );
+        res = res.add_attribute("withdrawn", ticket_addr);
+    }
+
+    Ok(res.add_messages(form_payment_msgs(deps.storage, &info.sender, refund)?))
 }
 
-/// counts how many tickets are purchased, returning any overflow amounts sent.
-fn count_tickets_and_remainder(
-    dev_addr: String,
-    funds_sent: &[Coin],
-    gd_ticket_cost: Vec<Coin>,
-    reap: &[RegisteringEventAddressAndPayment],
-) -> (u128, Vec<Coin>, CosmosMsg) {
+/// Entry point for cw20 tokens sent to purchase tickets.
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // sender is the token contract, which must be accepted as ticket payment
+    let token = info.sender.to_string();
+    if !CW20_PAYMENT_TOKENS.has(deps.storage, &token) {
+        return Err(ContractError::UnsupportedCw20Token {});
+    }
+    let purchaser = deps.api.addr_validate(&wrapper.sender)?;
+    let funds = vec![coin(wrapper.amount.u128(), token)];
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::PurchaseTickets { guests } => {
+            perform_ticket_purchase(deps, env, purchaser, funds, guests)
+        }
+        ReceiveMsg::BuyResaleTicket {
+            ticket_addr,
+            new_ticket_addr,
+        } => perform_buy_resale_ticket(deps, env, purchaser, funds, ticket_addr, new_ticket_addr),
+    }
+}
+
+/// wallet holding a ticket: the purchaser while it is still reserved for a homie, otherwise the ticket address.
+fn ticket_holder(
+    storage: &dyn Storage,
+    ticket_addr: &String,
+    receipt: &TicketReceipt,
+) -> StdResult<Addr> {
+    let reserved_for_homie = HOMIE_TICKETS
+        .may_load(storage, &receipt.purchaser.to_string())?
+        .is_some_and(|homies| homies.contains(ticket_addr));
+    Ok(match reserved_for_homie {
+        true => receipt.purchaser.clone(),
+        false => Addr::unchecked(ticket_addr),
+    })
+}
+
+/// Entry point to list a ticket for resale.
+/// Prices must be in a denom accepted for the guest type, and within the resale price cap of the event.
+pub fn perform_list_ticket_for_resale(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ticket_addr: String,
+    price: Coin,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    ensure_phase(deps.storage, env.block.time, SALE_PHASES)?;
+    let receipt = ticket_receipts()
+        .may_load(deps.storage, &ticket_addr)?
+        .ok_or(ContractError::NoReservedTicketsForGuest {})?;
+    if info.sender != ticket_holder(deps.storage, &ticket_addr, &receipt)? {
+        return Err(ContractError::NotTicketHolder {});
+    }
+    if RESALE_LISTINGS.has(deps.storage, &ticket_addr) {
+        return Err(ContractError::TicketListedForResale {});
+    }
+    // resold tickets move to a new address in the guest group
+    if !cfg.guest_group.writable() {
+        return Err(ContractError::GuestGroupNotWritable {});
+    }
+    // tickets already used to checkin cannot be resold
+    if attendance_records()
+        .prefix(&ticket_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .any(|r| r.is_ok_and(|(_, record)| record.checked_in))
+    {
+        return Err(ContractError::GuestAlreadyCheckedIn {});
+    }
+
+    ensure_valid_resale_price(deps.storage, &cfg, &receipt, &price)?;
+
+    RESALE_LISTINGS.save(
+        deps.storage,
+        &ticket_addr,
+        &ResaleListing {
+            seller: info.sender,
+            guest_weight: receipt.guest_weight,
+            price: price.clone(),
+            listed_at: env.block.time,
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "list_ticket_for_resale")
+        .add_attribute("ticket_addr", ticket_addr)
+        .add_attribute("price", price.to_string()))
+}
+
+/// ensures a ticket is resold in a denom accepted for its guest type, for no more than the resale cap
+/// of its face value. The face value is what was paid for the ticket, converted to other denoms
+/// by the prices of the guest type.
+fn ensure_valid_resale_price(
+    storage: &dyn Storage,
+    cfg: &Config,
+    receipt: &TicketReceipt,
+    price: &Coin,
+) -> Result<(), ContractError> {
+    let options = GUEST_DETAILS
+        .load(storage, receipt.guest_weight)?
+        .payment_options();
+    let listed_cost = options
+        .iter()
+        .find(|c| c.denom == price.denom)
+        .ok_or(ContractError::InvalidResalePrice {})?;
+    if price.amount.is_zero() {
+        return Err(ContractError::InvalidResalePrice {});
+    }
+    if let Some(max_price_bps) = cfg.resale.max_price_bps {
+        let face_value = if price.denom == receipt.denom {
+            receipt.price
+        } else {
+            // priced relative to the paid denom, so price changes after purchase do not move the cap
+            match options.iter().find(|c| c.denom == receipt.denom) {
+                Some(paid_cost) if !paid_cost.amount.is_zero() => listed_cost
+                    .amount
+                    .multiply_ratio(receipt.price, paid_cost.amount),
+                _ => listed_cost.amount,
+            }
+        };
+        let max = face_value.multiply_ratio(max_price_bps, MAX_BPS);
+        if price.amount > max {
+            return Err(ContractError::ResalePriceAboveCap {
+                max: coin(max.u128(), &price.denom),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Entry point to remove a resale listing, callable by the seller.
+pub fn perform_cancel_resale_listing(
+    deps: DepsMut,
+    info: MessageInfo,
+    ticket_addr: String,
+) -> Result<Response, ContractError> {
+    let listing = RESALE_LISTINGS
+        .may_load(deps.storage, &ticket_addr)?
+        .ok_or(ContractError::NotListedForResale {})?;
+    if info.sender != listing.seller {
+        return Err(ContractError::NotTicketHolder {});
+    }
+    RESALE_LISTINGS.remove(deps.storage, &ticket_addr);
+    Ok(Response::new()
+        .add_attribute("action", "cancel_resale_listing")
+        .add_attribute("ticket_addr", ticket_addr))
+}
+
+/// Entry point to buy a ticket listed for resale.
+/// The ticket, and the right to refund it, move to the buyer, the seller is paid the price minus the curator royalty.
+pub fn perform_buy_resale_ticket(
+    deps: DepsMut,
+    env: Env,
+    buyer: Addr,
+    funds: Vec<Coin>,
+    ticket_addr: String,
+    new_ticket_addr: String,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    ensure_phase(deps.storage, env.block.time, SALE_PHASES)?;
+    let listing = RESALE_LISTINGS
+        .may_load(deps.storage, &ticket_addr)?
+        .ok_or(ContractError::NotListedForResale {})?;
+    let new_ticket_addr = deps.api.addr_validate(&new_ticket_addr)?.to_string();
+    let receipt = ticket_receipts().load(deps.storage, &ticket_addr)?;
+    ensure_valid_resale_price(deps.storage, &cfg, &receipt, &listing.price)?;
+
+    let mut change = funds;
+    match change.iter_mut().find(|c| c.denom == listing.price.denom) {
+        Some(paid) if paid.amount >= listing.price.amount => paid.amount -= listing.price.amount,
+        _ => return Err(ContractError::InsufficientResalePayment {}),
+    }
+    RESALE_LISTINGS.remove(deps.storage, &ticket_addr);
+
+    // the ticket is no longer reserved by its previous purchaser
+    let gd = GUEST_DETAILS.load(deps.storage, listing.guest_weight)?;
+    let previous_purchaser = receipt.purchaser.to_string();
+    if let Some(mut homies) = HOMIE_TICKETS.may_load(deps.storage, &previous_purchaser)? {
+        homies.retain(|h| h != &ticket_addr);
+        match homies.is_empty() {
+            true => HOMIE_TICKETS.remove(deps.storage, &previous_purchaser),
+            false => HOMIE_TICKETS.save(deps.storage, &previous_purchaser, &homies)?,
+        }
+    }
+    release_reserved_ticket(deps.storage, &receipt.purchaser, gd.guest_weight)?;
+    reserve_tickets(deps.storage, &buyer, &gd, 1)?;
+
+    move_ticket(deps.storage, &ticket_addr, &new_ticket_addr)?;
+    ticket_receipts().save(
+        deps.storage,
+        &new_ticket_addr,
+        &TicketReceipt {
+            purchaser: buyer.clone(),
+            ..receipt
+        },
+    )?;
+    if new_ticket_addr != buyer.as_str() {
+        HOMIE_TICKETS.update(deps.storage, &buyer.to_string(), |homies| {
+            let mut homies = homies.unwrap_or_default();
+            homies.push(new_ticket_addr.clone());
+            Ok::<Vec<String>, StdError>(homies)
+        })?;
+    }
+
+    let royalty = listing
+        .price
+        .amount
+        .multiply_ratio(cfg.resale.royalty_bps, MAX_BPS);
+    let proceeds = listing.price.amount - royalty;
+    let denom = listing.price.denom;
+
+    Ok(Response::new()
+        .add_attribute("action", "buy_resale_ticket")
+        .add_attribute("ticket_addr", &ticket_addr)
+        .add_attribute("new_ticket_addr", &new_ticket_addr)
+        .add_attribute("royalty", coin(royalty.u128(), &denom).to_string())
+        .add_message(form_cw420_msg(
+            cfg.event_guest_contract.to_string(),
+            vec![Member {
+                addr: new_ticket_addr,
+                weight: gd.guest_weight,
+            }],
+            vec![ticket_addr],
+        )?)
+        .add_messages(form_payment_msgs(
+            deps.storage,
+            &listing.seller,
+            vec![coin(proceeds.u128(), &denom)],
+        )?)
+        .add_messages(form_payment_msgs(
+            deps.storage,
+            &cfg.curator,
+            vec![coin(royalty.u128(), &denom)],
+        )?)
+        .add_messages(form_payment_msgs(deps.storage, &buyer, change)?))
+}
+
+/// Entry point to commit to a secret a homie can use to check in with a ticket.
+pub fn perform_set_homie_checkin_secret(
+    deps: DepsMut,
+    info: MessageInfo,
+    ticket_addr: String,
+    secret_hash: Binary,
+) -> Result<Response, ContractError> {
+    let receipt = ticket_receipts()
+        .may_load(deps.storage, &ticket_addr)?
+        .ok_or(ContractError::NoReservedTicketsForGuest {})?;
+    if info.sender != ticket_holder(deps.storage, &ticket_addr, &receipt)? {
+        return Err(ContractError::NotTicketHolder {});
+    }
+    if secret_hash.len() != 32 {
+        return Err(ContractError::InvalidHomieCheckinSecretHash {});
+    }
+
+    HOMIE_CHECKIN_SECRETS.save(
+        deps.storage,
+        &ticket_addr,
+        &HomieCheckinSecret {
+            secret_hash,
+            set_by: info.sender,
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "set_homie_checkin_secret")
+        .add_attribute("ticket_addr", ticket_addr))
+}
+
+/// Entry point to remove the homie checkin secret of a ticket.
+pub fn perform_revoke_homie_checkin_secret(
+    deps: DepsMut,
+    info: MessageInfo,
+    ticket_addr: String,
+) -> Result<Response, ContractError> {
+    let receipt = ticket_receipts()
+        .may_load(deps.storage, &ticket_addr)?
+        .ok_or(ContractError::NoReservedTicketsForGuest {})?;
+    if info.sender != ticket_holder(deps.storage, &ticket_addr, &receipt)? {
+        return Err(ContractError::NotTicketHolder {});
+    }
+    if !HOMIE_CHECKIN_SECRETS.has(deps.storage, &ticket_addr) {
+        return Err(ContractError::NoHomieCheckinSecret {});
+    }
+
+    HOMIE_CHECKIN_SECRETS.remove(deps.storage, &ticket_addr);
+    Ok(Response::new()
+        .add_attribute("action", "revoke_homie_checkin_secret")
+        .add_attribute("ticket_addr", ticket_addr))
+}
+
+/// Entry point for ushers to checkin a ticket with the secret its holder committed to.
+/// The secret is used up once checked in.
+pub fn perform_checkin_with_homie_secret(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ticket_addr: String,
+    secret: String,
+    event_segment_ids: Vec<u64>,
+    gate: Option<String>,
+) -> Result<Response, ContractError> {
+    // sender must be one of event ushers
+    let cfg = CONFIG.load(deps.storage)?;
+    ensure_phase(deps.storage, env.block.time, ATTENDANCE_PHASES)?;
+    let usher = load_usher(deps.as_ref(), &cfg, &info.sender)?;
+    validate_gate_label(gate.as_ref())?;
+
+    let commitment = HOMIE_CHECKIN_SECRETS
+        .may_load(deps.storage, &ticket_addr)?
+        .ok_or(ContractError::NoHomieCheckinSecret {})?;
+    if sha256(secret.as_bytes()) != commitment.secret_hash.as_slice() {
+        return Err(ContractError::IncorrectHomieCheckinSecret {});
+    }
+    HOMIE_CHECKIN_SECRETS.remove(deps.storage, &ticket_addr);
+
+    let (segment_ids, mints) = checkin_ticket(
+        deps.branch(),
+        &env.block,
+        &cfg,
+        &ticket_addr,
+        &event_segment_ids,
+        &CheckinBy {
+            usher: &usher,
+            gate: gate.as_ref(),
+            supervisor_override: false,
+        },
+    )?;
+    let mut res = Response::new()
+        .add_messages(mints)
+        .add_attribute("action", "checkin_with_homie_secret")
+        .add_attribute("ticket_addr", ticket_addr)
+        .add_attribute("event_segment_ids", join_ids(&segment_ids));
+    if let Some(gate) = gate {
+        res = res.add_attribute("gate", gate);
+    }
+    Ok(res)
+}
+
+/// Entry point to checkin guests as event usher
+pub fn perform_checkin_guest(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    checkin: CheckInDetails,
+) -> Result<Response, ContractError> {
+    // sender must be one of event ushers
+    let cfg = CONFIG.load(deps.storage)?;
+    ensure_phase(deps.storage, env.block.time, ATTENDANCE_PHASES)?;
+    let usher = load_usher(deps.as_ref(), &cfg, &info.sender)?;
+
+    let (attrs, mints) = checkin_guest(deps, &env, &usher, &cfg, checkin)?;
+    Ok(Response::new()
+        .add_messages(mints)
+        .add_attribute("action", "checkin_guest")
+        .add_attributes(attrs))
+}
+
+/// Entry point to checkin many guests at once as event usher.
+/// Depending on `mode`, the first invalid checkin either fails the batch, or is skipped & reported.
+pub fn perform_batch_checkin_guests(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    checkins: Vec<CheckInDetails>,
+    mode: BatchCheckInMode,
+) -> Result<Response, ContractError> {
+    if checkins.len() > MAX_BATCH_CHECKINS {
+        return Err(ContractError::TooManyCheckins {
+            max: MAX_BATCH_CHECKINS,
+        });
+    }
+    // sender must be one of event ushers, checked once for the whole batch
+    let cfg = CONFIG.load(deps.storage)?;
+    ensure_phase(deps.storage, env.block.time, ATTENDANCE_PHASES)?;
+    let usher = load_usher(deps.as_ref(), &cfg, &info.sender)?;
+
+    let mut res = Response::new().add_attribute("action", "batch_checkin_guests");
+    let mut skipped = 0;
+    for (index, checkin) in checkins.into_iter().enumerate() {
+        let ticket_addr = checkin.ticket_addr.clone();
+        match checkin_guest(deps.branch(), &env, &usher, &cfg, checkin) {
+            Ok((attrs, mints)) => res = res.add_messages(mints).add_attributes(attrs),
+            Err(err) => match mode {
+                BatchCheckInMode::FailFast => {
+                    return Err(ContractError::BatchCheckinFailed {
+                        index,
+                        reason: err.to_string(),
+                    })
+                }
+                BatchCheckInMode::SkipInvalid => {
+                    skipped += 1;
+                    res = res.add_attribute(
+                        "checkin_skipped",
+                        format!("{}:{}:{}", index, ticket_addr, err),
+                    );
+                }
+            },
+        }
+    }
+    Ok(res.add_attribute("skipped", skipped.to_string()))
+}
+
+/// Entry point for the curator to edit the event, validated as when it was created.
+/// Changes that would hurt ticket holders, like fewer tickets than sold or moving segments that started, are refused.
+#[allow(clippy::too_many_arguments)]
+pub fn perform_update_event(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: Option<String>,
+    description: Option<String>,
+    add_guest_types: Vec<GuestDetails>,
+    update_guest_types: Vec<GuestTypeUpdate>,
+    reschedule_segments: Vec<SegmentSchedule>,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+    if info.sender != cfg.curator {
+        return Err(ContractError::NotEventCurator {});
+    }
+    ensure_phase(deps.storage, env.block.time, EDIT_PHASES)?;
+    cfg.title = title.unwrap_or(cfg.title);
+    cfg.description = description.unwrap_or(cfg.description);
+    if cfg.title.len() > CHARACTER_LIMIT || cfg.description.len() > CHARACTER_LIMIT {
+        return Err(ContractError::BadEventTitleOrDescription {});
+    }
+    CONFIG.save(deps.storage, &cfg)?;
+    let mut attrs = vec![attr("action", "update_event")];
+    let mut msgs = vec![];
+
+    // segments are rescheduled first, so guest types are validated against the new timeline
+    if !reschedule_segments.is_empty() {
+        let mut timeline = EVENT_STAGES
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, segment)| segment))
+            .collect::<StdResult<Vec<_>>>()?;
+        for schedule in &reschedule_segments {
+            let segment = timeline
+                .get_mut(schedule.segment_id as usize)
+                .ok_or(ContractError::IncorrectEventSegmentId {})?;
+            // checkins & escrow releases rely on the times of segments that started
+            if segment.start <= env.block.time || schedule.start <= env.block.time {
+                return Err(ContractError::CannotRescheduleSegment {});
+            }
+            segment.start = schedule.start;
+            segment.end = schedule.end;
+            attrs.push(attr("rescheduled_segment", schedule.segment_id.to_string()));
+        }
+        validate_event_timeline(&timeline)?;
+        for (id, segment) in timeline.iter().enumerate() {
+            EVENT_STAGES.save(deps.storage, id as u64, segment)?;
+        }
+    }
+
+    for gd in add_guest_types {
+        if GUEST_DETAILS.has(deps.storage, gd.guest_weight) {
+            return Err(ContractError::DuplicateGuestWeight {});
+        }
+        validate_guest_details(deps.branch(), &gd)?;
+        ensure_segment_access_exists(deps.storage, &gd)?;
+        GUEST_DETAILS.save(deps.storage, gd.guest_weight, &gd)?;
+        TOTAL_RESERVED_BY_GUEST_TYPE.save(deps.storage, gd.guest_weight, &0)?;
+        attrs.push(attr("added_guest_type", gd.guest_weight.to_string()));
+    }
+
+    let guest_types_updated = !update_guest_types.is_empty();
+    for update in update_guest_types {
+        let mut gd = GUEST_DETAILS
+            .may_load(deps.storage, update.guest_weight)?
+            .ok_or(ContractError::GuestTypeIncorrect {})?;
+        gd.total_ticket_limit = update.total_ticket_limit.unwrap_or(gd.total_ticket_limit);
+        gd.max_ticket_limit = update.max_ticket_limit.unwrap_or(gd.max_ticket_limit);
+        // new prices only apply to tickets sold after, receipts keep what was paid
+        gd.ticket_cost = update.ticket_cost.unwrap_or(gd.ticket_cost);
+        gd.cw20_ticket_cost = update.cw20_ticket_cost.unwrap_or(gd.cw20_ticket_cost);
+        validate_guest_details(deps.branch(), &gd)?;
+        let sold = TOTAL_RESERVED_BY_GUEST_TYPE.load(deps.storage, gd.guest_weight)?;
+        if gd.total_ticket_limit < sold {
+            return Err(ContractError::TicketLimitBelowSold { sold });
+        }
+        GUEST_DETAILS.save(deps.storage, gd.guest_weight, &gd)?;
+        attrs.push(attr("updated_guest_type", gd.guest_weight.to_string()));
+
+        // tickets added to a sold out guest type go to its waitlist first
+        let (promoted, promote_msgs) = promote_from_waitlist(deps.storage, gd.guest_weight, &cfg)?;
+        attrs.extend(promoted);
+        msgs.extend(promote_msgs);
+    }
+    // revenue shares paid per ticket must stay in denoms accepted for their guest type
+    if guest_types_updated {
+        validate_revenue_splits(deps.as_ref(), cfg.revenue_splits)?;
+    }
+
+    Ok(Response::new().add_attributes(attrs).add_messages(msgs))
+}
+
+/// Entry point for the event curator to set the checkin grace periods of an event segment.
+pub fn perform_update_checkin_grace_periods(
+    deps: DepsMut,
+    info: MessageInfo,
+    segment_id: u64,
+    early_checkin_grace: Option<u64>,
+    late_checkin_grace: Option<u64>,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.curator {
+        return Err(ContractError::NotEventCurator {});
+    }
+    let mut segment = EVENT_STAGES
+        .may_load(deps.storage, segment_id)?
+        .ok_or(ContractError::IncorrectEventSegmentId {})?;
+    segment.early_checkin_grace = early_checkin_grace;
+    segment.late_checkin_grace = late_checkin_grace;
+    EVENT_STAGES.save(deps.storage, segment_id, &segment)?;
+
+    let (opens, closes) = segment.checkin_window();
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_checkin_grace_periods"),
+        attr("segment_id", segment_id.to_string()),
+        attr("checkin_opens", opens.to_string()),
+        attr("checkin_closes", closes.to_string()),
+    ]))
+}
+
+/// Entry point for the curator to add & remove event ushers.
+pub fn perform_update_ushers(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<Member>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let cfg = ensure_usher_group_curator(deps.as_ref(), &info)?;
+    let mut res = Response::new();
+    let mut to_add = Vec::with_capacity(add.len());
+    for usher in add {
+        let addr = deps.api.addr_validate(&usher.addr)?;
+        if to_add.iter().any(|m: &Member| m.addr == addr.as_str())
+            || check_if_cw420_member(deps.as_ref(), &cfg.event_usher_contract, &addr)?.is_some()
+        {
+            return Err(ContractError::UsherAlreadyExists {});
+        }
+        res = res.add_attribute("usher_added", addr.as_str());
+        to_add.push(Member {
+            addr: addr.to_string(),
+            weight: usher.weight,
+        });
+    }
+    let mut to_remove = Vec::with_capacity(remove.len());
+    for usher in remove {
+        let addr = deps.api.addr_validate(&usher)?;
+        if to_remove.contains(&addr.to_string())
+            || check_if_cw420_member(deps.as_ref(), &cfg.event_usher_contract, &addr)?.is_none()
+        {
+            return Err(ContractError::UsherNotFound {});
+        }
+        USHER_SCOPES.remove(deps.storage, &addr);
+        res = res.add_attribute("usher_removed", addr.as_str());
+        to_remove.push(addr.to_string());
+    }
+
+    Ok(res
+        .add_attribute("action", "update_ushers")
+        .add_message(form_cw420_msg(
+            cfg.event_usher_contract.to_string(),
+            to_add,
+            to_remove,
+        )?))
+}
+
+/// Entry point for the curator to update the weights of existing event ushers.
+pub fn perform_update_usher_weights(
+    deps: DepsMut,
+    info: MessageInfo,
+    ushers: Vec<Member>,
+) -> Result<Response, ContractError> {
+    let cfg = ensure_usher_group_curator(deps.as_ref(), &info)?;
+    let mut res = Response::new().add_attribute("action", "update_usher_weights");
+    let mut updated = Vec::with_capacity(ushers.len());
+    for usher in ushers {
+        let addr = deps.api.addr_validate(&usher.addr)?;
+        if check_if_cw420_member(deps.as_ref(), &cfg.event_usher_contract, &addr)?.is_none() {
+            return Err(ContractError::UsherNotFound {});
+        }
+        res = res.add_attribute("usher_weight", format!("{}:{}", addr, usher.weight));
+        updated.push(Member {
+            addr: addr.to_string(),
+            weight: usher.weight,
+        });
+    }
+    Ok(res.add_message(form_cw420_msg(
+        cfg.event_usher_contract.to_string(),
+        updated,
+        vec![],
+    )?))
+}
+
+/// Entry point for the curator to restrict the segments & gates an usher can checkin guests at.
+pub fn perform_set_usher_scope(
+    deps: DepsMut,
+    info: MessageInfo,
+    usher: String,
+    scope: Option<UsherScope>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if info.sender != cfg.curator {
+        return Err(ContractError::NotEventCurator {});
+    }
+    let usher = deps.api.addr_validate(&usher)?;
+    if check_if_cw420_member(deps.as_ref(), &cfg.event_usher_contract, &usher)?.is_none() {
+        return Err(ContractError::UsherNotFound {});
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "set_usher_scope")
+        .add_attribute("usher", usher.as_str());
+    let Some(scope) = scope else {
+        USHER_SCOPES.remove(deps.storage, &usher);
+        return Ok(res);
+    };
+    if scope
+        .segment_ids
+        .iter()
+        .any(|id| !EVENT_STAGES.has(deps.storage, *id))
+    {
+        return Err(ContractError::IncorrectEventSegmentId {});
+    }
+    for gate in &scope.gates {
+        validate_gate_label(Some(gate))?;
+    }
+    USHER_SCOPES.save(deps.storage, &usher, &scope)?;
+    Ok(res
+        .add_attribute("segment_ids", join_ids(&scope.segment_ids))
+        .add_attribute("gates", scope.gates.join(",")))
+}
+
+/// an event usher checking in guests, with the role & scope they have for this event.
+struct Usher {
+    addr: Addr,
+    supervisor: bool,
+    scope: Option<UsherScope>,
+}
+
+/// who is checking in tickets, at which gate.
+struct CheckinBy<'a> {
+    usher: &'a Usher,
+    gate: Option<&'a String>,
+    /// skip the checkin windows of the event segments, only set by supervisors
+    supervisor_override: bool,
+}
+
+/// loads an usher of the event, erroring if the wallet is not a member of the usher group.
+fn load_usher(deps: Deps, cfg: &Config, wallet: &Addr) -> Result<Usher, ContractError> {
+    let weight = check_if_cw420_member(deps, &cfg.event_usher_contract, wallet)?
+        .ok_or(ContractError::NotAnEventUsher {})?;
+    Ok(Usher {
+        addr: wallet.clone(),
+        supervisor: cfg.supervisor_weight.is_some_and(|min| weight >= min),
+        scope: USHER_SCOPES.may_load(deps.storage, wallet)?,
+    })
+}
+
+fn validate_gate_label(gate: Option<&String>) -> Result<(), ContractError> {
+    match gate {
+        Some(gate) if gate.is_empty() || gate.len() > CHARACTER_LIMIT => {
+            Err(ContractError::InvalidGateLabel {})
+        }
+        _ => Ok(()),
+    }
+}
+
+/// ensures the sender is the event curator, and the event can update the members of its usher group.
+fn ensure_usher_group_curator(deps: Deps, info: &MessageInfo) -> Result<Config, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if info.sender != cfg.curator {
+        return Err(ContractError::NotEventCurator {});
+    }
+    if !cfg.usher_group.writable() {
+        return Err(ContractError::UsherGroupNotWritable {});
+    }
+    Ok(cfg)
+}
+
+/// verifies a signed checkin & checks in its tickets, returning the attributes describing the result
+/// & the messages minting attendance tokens. nothing is written if an error is returned.
+fn checkin_guest(
+    mut deps: DepsMut,
+    env: &Env,
+    usher: &Usher,
+    cfg: &Config,
+    checkin: CheckInDetails,
+) -> Result<(Vec<Attribute>, Vec<CosmosMsg>), ContractError> {
+    if checkin.supervisor_override && !usher.supervisor {
+        return Err(ContractError::NotASupervisor {});
+    }
+    validate_gate_label(checkin.gate.as_ref())?;
+    let by = CheckinBy {
+        usher,
+        gate: checkin.gate.as_ref(),
+        supervisor_override: checkin.supervisor_override,
+    };
+
+    // verify signature came from the key of the ticket address and is valid
+    verify_checkin_signature(deps.api, &checkin, cfg.webauthn_rp_id.as_deref())?;
+
+    // parse signed_data to retrieve the segments being checked into
+    let signature_data = parse_checkin_signature_data(&checkin.signed_data)?;
+    validate_checkin_signature_data(
+        deps.storage,
+        env,
+        &usher.addr,
+        &checkin.ticket_addr,
+        &signature_data,
+    )?;
+
+    let mut attrs = vec![attr("ticket_addr", &checkin.ticket_addr)];
+    if let Some(gate) = &checkin.gate {
+        attrs.push(attr("gate", gate));
+    }
+
+    let mut mints = vec![];
+    if signature_data.homies_tickets.is_empty() {
+        let (segment_ids, ticket_mints) = checkin_ticket(
+            deps.branch(),
+            &env.block,
+            cfg,
+            &checkin.ticket_addr,
+            &signature_data.event_segment_ids,
+            &by,
+        )?;
+        mints.extend(ticket_mints);
+        attrs.push(attr("event_segment_ids", join_ids(&segment_ids)));
+    } else {
+        // group checkin of the signer & the homies they reserved tickets for, that have not claimed them yet.
+        // each ticket is checked in on its own, with the result of each reported.
+        if signature_data.homies_tickets.len() > 10 {
+            return Err(ContractError::TooManyHomieTickets {});
+        }
+        let reserved = HOMIE_TICKETS
+            .may_load(deps.storage, &checkin.ticket_addr)?
+            .unwrap_or_default();
+        let mut tickets = vec![checkin.ticket_addr.clone()];
+        for homie in signature_data.homies_tickets {
+            if !tickets.contains(&homie) {
+                tickets.push(homie);
+            }
+        }
+
+        let mut checked_in = 0;
+        for (i, ticket_addr) in tickets.iter().enumerate() {
+            let result = if i > 0 && !reserved.contains(ticket_addr) {
+                Err(ContractError::NoReservedTicketsForGuest {})
+            } else {
+                checkin_ticket(
+                    deps.branch(),
+                    &env.block,
+                    cfg,
+                    ticket_addr,
+                    &signature_data.event_segment_ids,
+                    &by,
+                )
+            };
+            let outcome = match result {
+                Ok((segment_ids, ticket_mints)) => {
+                    checked_in += 1;
+                    mints.extend(ticket_mints);
+                    format!("{}:checked_in:{}", ticket_addr, join_ids(&segment_ids))
+                }
+                Err(err) => format!("{}:failed:{}", ticket_addr, err),
+            };
+            attrs.push(attr("checkin_result", outcome));
+        }
+        if checked_in == 0 {
+            return Err(ContractError::NoTicketsCheckedIn {});
+        }
+    }
+
+    USED_CHECKIN_NONCES.save(
+        deps.storage,
+        (&checkin.ticket_addr, signature_data.nonce),
+        &true,
+    )?;
+
+    Ok((attrs, mints))
+}
+
+/// checks a ticket into the signed segments its guest type has access to,
+/// returning the segments checked into & the messages minting attendance tokens for first checkins.
+/// every segment is checked into, or none are.
+fn checkin_ticket(
+    deps: DepsMut,
+    block: &BlockInfo,
+    cfg: &Config,
+    ticket_addr: &String,
+    signed_ids: &[u64],
+    by: &CheckinBy,
+) -> Result<(Vec<u64>, Vec<CosmosMsg>), ContractError> {
+    let guest_weight = check_if_cw420_member(
+        deps.as_ref(),
+        &cfg.event_guest_contract,
+        &deps.api.addr_validate(ticket_addr)?,
+    )?
+    .ok_or(ContractError::GuestTypeIncorrect {})?;
+    if RESALE_LISTINGS.has(deps.storage, ticket_addr) {
+        return Err(ContractError::TicketListedForResale {});
+    }
+    let guest_details = GUEST_DETAILS.load(deps.storage, guest_weight)?;
+    let segment_ids = segments_to_checkin(&guest_details.event_segment_access, signed_ids)?;
+    // supervisors are not restricted by usher scopes
+    if let Some(scope) = by.usher.scope.as_ref().filter(|_| !by.usher.supervisor) {
+        ensure_within_usher_scope(scope, &segment_ids, by.gate)?;
+    }
+    if !by.supervisor_override {
+        ensure_within_checkin_windows(
+            deps.storage,
+            &guest_details.event_segment_access,
+            &segment_ids,
+            block.time,
+        )?;
+    }
+
+    let mut records = vec![];
+    for id in &segment_ids {
+        let record = attendance_records()
+            .may_load(deps.storage, (ticket_addr, *id))?
+            .ok_or(ContractError::IncorrectEventSegmentId {})?;
+        if CANCELLED_SEGMENTS.has(deps.storage, *id) {
+            return Err(ContractError::SegmentCancelled { segment_id: *id });
+        }
+        if record.inside() {
+            return Err(ContractError::GuestAlreadyCheckedIn {});
+        }
+        if record.checked_out && !guest_details.reentry.allows(record.reentries) {
+            return Err(ContractError::ReentryNotAllowed {});
+        }
+        // capacity applies to supervisor overrides too, and to guests checked in before the segment opens
+        let occupancy = OCCUPANCY.may_load(deps.storage, *id)?.unwrap_or_default()
+            + EARLY_OCCUPANCY
+                .may_load(deps.storage, *id)?
+                .unwrap_or_default();
+        let segment = EVENT_STAGES.load(deps.storage, *id)?;
+        if segment
+            .capacity
+            .is_some_and(|capacity| occupancy >= capacity)
+        {
+            return Err(ContractError::SegmentAtCapacity { segment_id: *id });
+        }
+        records.push((record, segment));
+    }
+    let mut mints = vec![];
+    for (record, segment) in records {
+        // attendance tokens are only minted on the first checkin, not re-entries
+        if let Some(collection) = cfg.poap_collection.as_ref().filter(|_| !record.checked_in) {
+            mints.push(form_poap_mint_msg(
+                collection,
+                cfg,
+                &record,
+                &segment,
+                &guest_details.guest_type,
+            )?);
+        }
+        update_attendance_record(
+            deps.storage,
+            record,
+            CheckInRecord {
+                time: block.time,
+                height: block.height,
+                usher: by.usher.addr.clone(),
+                gate: by.gate.cloned(),
+            },
+        )?;
+    }
+    Ok((segment_ids, mints))
+}
+
+/// forms the message minting the attendance token of a ticket for an event segment,
+/// described by the event title, segment description & guest type of the ticket.
+fn form_poap_mint_msg(
+    collection: &Addr,
+    cfg: &Config,
+    record: &AttendanceRecord,
+    segment: &EventSegment,
+    guest_type: &str,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_json_binary(&cw721_poap::msg::ExecuteMsg::Mint {
+            token_id: format!("{}/{}", record.segment_id, record.ticket_addr),
+            owner: record.ticket_addr.clone(),
+            token_uri: None,
+            extension: PoapMetadata {
+                event: cfg.title.clone(),
+                segment: segment.stage_description.clone(),
+                segment_id: record.segment_id,
+                guest_type: guest_type.to_string(),
+            },
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn join_ids(ids: &[u64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// records a guest entering an event segment, counting a re-entry if they had checked out.
+/// the checkin is added to the checkin log, after any earlier checkins of the ticket to the segment.
+pub fn update_attendance_record(
+    storage: &mut dyn Storage,
+    record: AttendanceRecord,
+    checkin: CheckInRecord,
+) -> StdResult<AttendanceRecord> {
+    let now = checkin.time;
+    let record = AttendanceRecord {
+        checked_in: true,
+        checkin: Some(checkin.clone()),
+        checked_out: false,
+        reentries: record.reentries + u32::from(record.checked_out),
+        ..record
+    };
+    attendance_records().save(
+        storage,
+        (&record.ticket_addr.clone(), record.segment_id),
+        &record,
+    )?;
+    checkin_log().save(
+        storage,
+        (&record.ticket_addr, record.segment_id, record.reentries),
+        &CheckInLogEntry {
+            ticket_addr: record.ticket_addr.clone(),
+            segment_id: record.segment_id,
+            seq: record.reentries,
+            checkin,
+        },
+    )?;
+    update_occupancy(storage, record.segment_id, now, true)?;
+    Ok(record)
+}
+
+/// counts a guest entering or leaving an event segment.
+/// guests are only counted in the occupancy of a segment once its checkin window opens,
+/// until then they are counted in its early occupancy.
+fn update_occupancy(
+    storage: &mut dyn Storage,
+    segment_id: u64,
+    now: Timestamp,
+    entering: bool,
+) -> StdResult<()> {
+    let segment = EVENT_STAGES.load(storage, segment_id)?;
+    let counter = match segment.checkin_window().0 <= now {
+        true => {
+            // guests checked in early are moved into the occupancy once the checkin window opens
+            let occupancy = segment_occupancy(storage, &segment, segment_id, now)?;
+            EARLY_OCCUPANCY.remove(storage, segment_id);
+            OCCUPANCY.save(storage, segment_id, &occupancy)?;
+            OCCUPANCY
+        }
+        false => EARLY_OCCUPANCY,
+    };
+    counter.update(storage, segment_id, |o| -> StdResult<_> {
+        let o = o.unwrap_or_default();
+        Ok(match entering {
+            true => o + 1,
+            false => o.saturating_sub(1),
+        })
+    })?;
+    Ok(())
+}
+
+/// guests inside an event segment. guests checked in before its checkin window opened are counted once it has.
+fn segment_occupancy(
+    storage: &dyn Storage,
+    segment: &EventSegment,
+    segment_id: u64,
+    now: Timestamp,
+) -> StdResult<u32> {
+    let occupancy = OCCUPANCY.may_load(storage, segment_id)?.unwrap_or_default();
+    if segment.checkin_window().0 > now {
+        return Ok(occupancy);
+    }
+    Ok(occupancy
+        + EARLY_OCCUPANCY
+            .may_load(storage, segment_id)?
+            .unwrap_or_default())
+}
+
+/// Entry point to check a guest out of event segments as event usher
+pub fn perform_checkout_guest(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ticket_addr: String,
+    event_segment_ids: Vec<u64>,
+    gate: Option<String>,
+) -> Result<Response, ContractError> {
+    // sender must be one of event ushers
+    let cfg = CONFIG.load(deps.storage)?;
+    let usher = load_usher(deps.as_ref(), &cfg, &info.sender)?;
+    validate_gate_label(gate.as_ref())?;
+    // supervisors are not restricted by usher scopes
+    if let Some(scope) = usher.scope.as_ref().filter(|_| !usher.supervisor) {
+        ensure_within_usher_scope(scope, &event_segment_ids, gate.as_ref())?;
+    }
+
+    let segment_ids = checkout_ticket(
+        deps.storage,
+        &ticket_addr,
+        event_segment_ids,
+        CheckOutRecord {
+            time: env.block.time,
+            height: env.block.height,
+            by: usher.addr,
+            gate: gate.clone(),
+        },
+    )?;
+    let mut res = Response::new()
+        .add_attribute("action", "checkout_guest")
+        .add_attribute("ticket_addr", ticket_addr)
+        .add_attribute("event_segment_ids", join_ids(&segment_ids));
+    if let Some(gate) = gate {
+        res = res.add_attribute("gate", gate);
+    }
+    Ok(res)
+}
+
+/// Entry point for a ticket to check itself out of event segments
+pub fn perform_checkout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    event_segment_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let ticket_addr = info.sender.to_string();
+    let segment_ids = checkout_ticket(
+        deps.storage,
+        &ticket_addr,
+        event_segment_ids,
+        CheckOutRecord {
+            time: env.block.time,
+            height: env.block.height,
+            by: info.sender,
+            gate: None,
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "checkout")
+        .add_attribute("ticket_addr", ticket_addr)
+        .add_attribute("event_segment_ids", join_ids(&segment_ids)))
+}
+
+/// checks a ticket out of event segments, returning the segments checked out of.
+/// the ticket must be inside every segment, or nothing is written.
+fn checkout_ticket(
+    storage: &mut dyn Storage,
+    ticket_addr: &String,
+    mut segment_ids: Vec<u64>,
+    checkout: CheckOutRecord,
+) -> Result<Vec<u64>, ContractError> {
+    segment_ids.sort_unstable();
+    segment_ids.dedup();
+    if segment_ids.is_empty() {
+        return Err(ContractError::IncorrectEventSegmentId {});
+    }
+    let mut records = vec![];
+    for id in &segment_ids {
+        let record = attendance_records()
+            .may_load(storage, (ticket_addr, *id))?
+            .ok_or(ContractError::IncorrectEventSegmentId {})?;
+        if !record.inside() {
+            return Err(ContractError::GuestNotCheckedIn {});
+        }
+        records.push(record);
+    }
+    for record in records {
+        attendance_records().save(
+            storage,
+            (ticket_addr, record.segment_id),
+            &AttendanceRecord {
+                checked_out: true,
+                checkout: Some(checkout.clone()),
+                ..record
+            },
+        )?;
+        update_occupancy(storage, record.segment_id, checkout.time, false)?;
+    }
+    Ok(segment_ids)
+}
+
+/// Refunds tickets that were never checked into, minus the non-refundable dev fee.
+/// Tickets go back on sale and their addresses are removed from the guest list.
+fn refund_unconfirmed_ticket_purchase(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    guests: Vec<String>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    ensure_phase(deps.storage, env.block.time, SALE_PHASES)?;
+    let mut refunds: Vec<(Addr, Vec<Coin>)> = vec![];
+    let mut to_remove = vec![];
+    let mut refunded_guest_types: Vec<u64> = vec![];
+    let mut res = Response::new().add_attribute("action", "refund_unconfirmed_tickets");
+
+    for ticket_addr in guests {
+        let receipt = ticket_receipts()
+            .may_load(deps.storage, &ticket_addr)?
+            .ok_or(ContractError::NoReservedTicketsForGuest {})?;
+        let purchaser = receipt.purchaser.to_string();
+
+        // purchasers can refund their own ticket, or tickets still reserved for their homies
+        let reserved_by_sender = info.sender == receipt.purchaser
+            && (ticket_addr == purchaser
+                || HOMIE_TICKETS
+                    .may_load(deps.storage, &purchaser)?
+                    .is_some_and(|homies| homies.contains(&ticket_addr)));
+        if !reserved_by_sender && info.sender != cfg.curator {
+            return Err(ContractError::NotTicketPurchaser {});
+        }
+
+        if RESALE_LISTINGS.has(deps.storage, &ticket_addr) {
+            return Err(ContractError::TicketListedForResale {});
+        }
+        // tickets already used to checkin are not refundable
+        if attendance_records()
+            .prefix(&ticket_addr)
+            .range(deps.storage, None, None, Order::Ascending)
+            .any(|r| r.is_ok_and(|(_, record)| record.checked_in))
+        {
+            return Err(ContractError::GuestAlreadyCheckedIn {});
+        }
+
+        // revenue is only refundable while none of it has been released from escrow
+        let gd = GUEST_DETAILS.load(deps.storage, receipt.guest_weight)?;
+        let (cancelled, _) = cancelled_escrow_weights(deps.storage, &gd.event_segment_access)?;
+        if cancelled > 0 {
+            return Err(ContractError::TicketSegmentsCancelled {});
+        }
+        let (released, _) =
+            released_escrow_weights(deps.storage, &gd.event_segment_access, env.block.time)?;
+        if released > 0 {
+            return Err(ContractError::RefundWindowClosed {});
+        }
+
+        let refund = receipt.price - receipt.dev_fee;
+        ESCROWED_REVENUE.update(
+            deps.storage,
+            (receipt.guest_weight, &receipt.denom),
+            |escrowed| -> StdResult<_> { Ok(escrowed.unwrap_or_default().checked_sub(refund)?) },
+        )?;
+
+        // tickets go back on sale
+        TOTAL_RESERVED_BY_GUEST_TYPE.update(deps.storage, receipt.guest_weight, |count| {
+            Ok::<u32, StdError>(count.unwrap_or_default().saturating_sub(1))
+        })?;
+        TICKETS_SOLD.update(
+            deps.storage,
+            (receipt.guest_weight, &receipt.denom),
+            |count| Ok::<u32, StdError>(count.unwrap_or_default().saturating_sub(1)),
+        )?;
+        release_reserved_ticket(deps.storage, &receipt.purchaser, receipt.guest_weight)?;
+        if let Some(mut homies) = HOMIE_TICKETS.may_load(deps.storage, &purchaser)? {
+            homies.retain(|h| h != &ticket_addr);
+            match homies.is_empty() {
+                true => HOMIE_TICKETS.remove(deps.storage, &purchaser),
+                false => HOMIE_TICKETS.save(deps.storage, &purchaser, &homies)?,
+            }
+        }
+        let segments = attendance_records()
+            .prefix(&ticket_addr)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+        for id in segments {
+            attendance_records().remove(deps.storage, (&ticket_addr, id))?;
+        }
+        ticket_receipts().remove(deps.storage, &ticket_addr)?;
+        HOMIE_CHECKIN_SECRETS.remove(deps.storage, &ticket_addr);
+        if !refunded_guest_types.contains(&receipt.guest_weight) {
+            refunded_guest_types.push(receipt.guest_weight);
+        }
+
+        res = res.add_attribute("refunded", &ticket_addr);
+        let refund = coin(refund.u128(), receipt.denom);
+        match refunds.iter_mut().find(|(p, _)| *p == receipt.purchaser) {
+            Some((_, coins)) => add_to_coins(coins, refund),
+            None => refunds.push((receipt.purchaser, vec![refund])),
+        }
+        to_remove.push(ticket_addr);
+    }
+
+    for (purchaser, amount) in refunds {
+        res = res.add_messages(form_payment_msgs(deps.storage, &purchaser, amount)?);
+    }
+    if cfg.guest_group.writable() {
+        res = res.add_message(form_cw420_msg(
+            cfg.event_guest_contract.to_string(),
+            vec![],
+            to_remove,
+        )?);
+    }
+
+    // refunded tickets go to the waitlist first
+    for guest_weight in refunded_guest_types {
+        let (attrs, msgs) = promote_from_waitlist(deps.storage, guest_weight, &cfg)?;
+        res = res.add_attributes(attrs).add_messages(msgs);
+    }
+    Ok(res)
+}
+
+/// (tickets purchased, leftover funds, receipts by ticket address, dev fees owed)
+type CountedTickets = (u128, Vec<Coin>, Vec<(String, TicketReceipt)>, Vec<Coin>);
+
+/// counts how many tickets are purchased, returning any overflow amounts sent,
+/// a receipt of what was paid for each ticket and the dev fee owed for them.
+fn count_tickets_and_remainder(
+    purchaser: &Addr,
+    guest_weight: u64,
+    env: &Env,
+    funds_sent: &[Coin],
+    gd_ticket_cost: Vec<Coin>,
+    reap: &[RegisteringEventAddressAndPayment],
+) -> CountedTickets {
     let mut remaining_funds = funds_sent.to_vec();
     let mut total_tickets = 0;
 
     let mut dev_fee_coins = Vec::new();
+    let mut receipts = Vec::new();
 
     for guest in reap {
         let denom = guest.payment_asset.clone();
@@ -440,9 +2411,18 @@ fn count_tickets_and_remainder(
                 // Check if sufficient funds are available
                 if fund.amount >= cost.amount {
                     // 3% flat fee
-                    dev_fee_coins.push(coin(
-                        cost.amount.multiply_ratio(3u128, 100u128).u128(),
-                        fund.denom.to_string(),
+                    let dev_fee = cost.amount.multiply_ratio(3u128, 100u128);
+                    add_to_coins(&mut dev_fee_coins, coin(dev_fee.u128(), &fund.denom));
+                    receipts.push((
+                        guest.ticket_addr.clone(),
+                        TicketReceipt {
+                            purchaser: purchaser.clone(),
+                            guest_weight,
+                            denom: cost.denom.clone(),
+                            price: cost.amount,
+                            dev_fee,
+                            purchased_at: env.block.time,
+                        },
                     ));
                     // Deduct payment and count ticket
                     fund.amount = fund.amount.checked_sub(cost.amount).unwrap();
@@ -454,20 +2434,13 @@ fn count_tickets_and_remainder(
 
     // Filter out zero-amount coins
     remaining_funds.retain(|coin| !coin.amount.is_zero());
-    (
-        total_tickets,
-        remaining_funds,
-        CosmosMsg::Bank(BankMsg::Send {
-            to_address: dev_addr,
-            amount: dev_fee_coins,
-        }),
-    )
+    (total_tickets, remaining_funds, receipts, dev_fee_coins)
 }
 
 fn form_update_guestlist_msg(
     sender: &Addr,
     storage: &mut dyn Storage,
-    guest_addrs: &[RegisteringEventAddressAndPayment],
+    guest_addrs: &[String],
     guest_weight: u64,
     gust_cw420: &Addr,
 ) -> Result<WasmMsg, ContractError> {
@@ -478,14 +2451,14 @@ fn form_update_guestlist_msg(
             add: guest_addrs
                 .iter()
                 .map(|a| -> Result<Member, StdError> {
-                    let ticket = a.ticket_addr.clone();
-                    if sender.as_str() != &ticket {
+                    let ticket = a.clone();
+                    if sender.as_str() != ticket {
                         HOMIE_TICKETS.update(storage, &sender.to_string(), |b| match b {
                             Some(mut c) => {
                                 if c.contains(&ticket) {
-                                    return Err(StdError::generic_err(
+                                    Err(StdError::generic_err(
                                         "This ticket is already registered for homies",
-                                    ));
+                                    ))
                                 } else {
                                     c.push(ticket.clone());
                                     Ok(c)
@@ -505,11 +2478,53 @@ fn form_update_guestlist_msg(
     })
 }
 
-fn form_return_payment_overflow_msgs(overflow: &Vec<Coin>, sender: &Addr) -> CosmosMsg {
-    CosmosMsg::Bank(BankMsg::Send {
-        to_address: sender.to_string(),
-        amount: overflow.clone(),
-    })
+/// sends `amount` to an address, as a bank send of native tokens & a transfer of each cw20 token.
+fn form_payment_msgs(
+    storage: &dyn Storage,
+    to_address: &Addr,
+    amount: Vec<Coin>,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut native = Vec::new();
+    let mut msgs = Vec::new();
+    for payment in amount.into_iter().filter(|c| !c.amount.is_zero()) {
+        if CW20_PAYMENT_TOKENS.has(storage, &payment.denom) {
+            msgs.push(
+                WasmMsg::Execute {
+                    contract_addr: payment.denom,
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: to_address.to_string(),
+                        amount: payment.amount,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        } else {
+            native.push(payment);
+        }
+    }
+    msgs.extend(form_bank_send_msg(to_address, native));
+    Ok(msgs)
+}
+
+/// bank send of `amount`, skipped when there is nothing to send as empty sends are rejected.
+fn form_bank_send_msg(to_address: &Addr, mut amount: Vec<Coin>) -> Option<CosmosMsg> {
+    amount.retain(|c| !c.amount.is_zero());
+    if amount.is_empty() {
+        return None;
+    }
+    Some(CosmosMsg::Bank(BankMsg::Send {
+        to_address: to_address.to_string(),
+        amount,
+    }))
+}
+
+/// adds a coin to a list of coins, merging amounts of the same denom.
+fn add_to_coins(coins: &mut Vec<Coin>, new: Coin) {
+    match coins.iter_mut().find(|c| c.denom == new.denom) {
+        Some(c) => c.amount += new.amount,
+        None => coins.push(new),
+    }
 }
 
 fn check_if_cw420_member(
@@ -529,7 +2544,8 @@ fn check_if_cw420_member(
     Ok(res.weight)
 }
 
-/// Entry point to claim funds sent for ticket payments
+/// Entry point to claim ticket payments released from escrow.
+/// Revenue of each guest type is released as the event segments it has access to end.
 pub fn perform_claim_ticket_payments(
     deps: DepsMut,
     env: Env,
@@ -537,30 +2553,256 @@ pub fn perform_claim_ticket_payments(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.curator {
-        return Err(ContractError::NotAnEventUsher {});
+        return Err(ContractError::NotEventCurator {});
+    }
+    if ensure_claims_not_frozen(deps.storage, env.block.time)? != EventPhase::Cancelled {
+        ensure_phase(deps.storage, env.block.time, ATTENDANCE_PHASES)?;
     }
 
-    // retrive all tokens to query
-    let tokens: Vec<String> = GUEST_DETAILS
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|res| res.ok())
-        .flat_map(|(_, guest_details)| guest_details.ticket_cost.into_iter().map(|c| c.denom))
-        .fold(vec![], |mut acc, denom| {
-            if !acc.contains(&denom) {
-                acc.push(denom);
-            }
-            acc
-        });
+    let res = Response::new().add_attribute("action", "claim_ticket_payments");
+    claim_released_revenue(deps, &env, &config, res)
+}
 
-    let balances: Vec<Coin> = tokens
-        .iter()
-        .map(|denom| deps.querier.query_balance(&env.contract.address, denom))
-        .collect::<StdResult<Vec<Coin>>>()?;
+/// errors while claims of a cancelled event are frozen, for ticket holders to claim their refunds.
+/// returns the current phase of the event.
+fn ensure_claims_not_frozen(
+    storage: &dyn Storage,
+    now: Timestamp,
+) -> Result<EventPhase, ContractError> {
+    let phase = event_status(storage, now)?.phase;
+    if phase == EventPhase::Cancelled && refund_window_open(storage, now)? {
+        return Err(ContractError::RefundWindowOpen {});
+    }
+    Ok(phase)
+}
 
-    Ok(Response::new().add_message(BankMsg::Send {
-        to_address: config.curator.to_string(),
-        amount: balances,
-    }))
+/// Entry point for revenue split payees to withdraw their share of released ticket revenue in a denom.
+pub fn perform_withdraw_revenue_share(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.revenue_splits.iter().any(|s| s.payee == info.sender) {
+        return Err(ContractError::NotARevenuePayee {});
+    }
+    ensure_claims_not_frozen(deps.storage, env.block.time)?;
+
+    let accrued = payee_revenue(deps.storage, &config.revenue_splits, env.block.time)?
+        .into_iter()
+        .find(|p| p.payee == info.sender)
+        .and_then(|p| p.accrued.into_iter().find(|c| c.denom == denom))
+        .map_or(Uint128::zero(), |c| c.amount);
+    let withdrawn = WITHDRAWN_REVENUE_SHARES
+        .may_load(deps.storage, (&info.sender, &denom))?
+        .unwrap_or_default();
+    let amount = accrued.saturating_sub(withdrawn);
+    if !amount.is_zero() {
+        WITHDRAWN_REVENUE_SHARES.save(
+            deps.storage,
+            (&info.sender, &denom),
+            &(withdrawn + amount),
+        )?;
+    }
+
+    let payment = coin(amount.u128(), denom);
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_revenue_share")
+        .add_attribute("payee", info.sender.to_string())
+        .add_attribute("withdrawn", payment.to_string())
+        .add_messages(form_payment_msgs(
+            deps.storage,
+            &info.sender,
+            vec![payment],
+        )?))
+}
+
+/// pays the curator all ticket revenue released from escrow & not yet claimed,
+/// along with cancellation refunds left unclaimed once the refund window closed.
+fn claim_released_revenue(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    mut res: Response,
+) -> Result<Response, ContractError> {
+    let mut payout = take_expired_refunds(deps.storage, env.block.time)?;
+    for refund in &payout {
+        res = res.add_attribute("unclaimed_refunds", refund.to_string());
+    }
+    let claimable = escrow_status(deps.storage, env.block.time)?.claimable;
+    for payment in &claimable {
+        CLAIMED_REVENUE.update(deps.storage, &payment.denom, |c| -> StdResult<_> {
+            Ok(c.unwrap_or_default().checked_add(payment.amount)?)
+        })?;
+    }
+
+    for payment in claimable {
+        res = res.add_attribute("claimed", payment.to_string());
+        add_to_coins(&mut payout, payment);
+    }
+    Ok(res.add_messages(form_payment_msgs(deps.storage, &config.curator, payout)?))
+}
+
+/// Entry point for the curator to put a draft event on sale.
+pub fn perform_open_sales(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.curator {
+        return Err(ContractError::NotEventCurator {});
+    }
+    ensure_phase(deps.storage, env.block.time, &[EventPhase::Draft])?;
+    config.lifecycle = LifecycleState::Published;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "open_sales"))
+}
+
+/// Entry point for the curator to settle an event once its last segment ended.
+/// Every segment has ended, so all escrowed ticket revenue is released & paid out.
+pub fn perform_settle_event(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.curator {
+        return Err(ContractError::NotEventCurator {});
+    }
+    ensure_phase(deps.storage, env.block.time, &[EventPhase::Ended])?;
+    if refund_window_open(deps.storage, env.block.time)? {
+        return Err(ContractError::RefundWindowOpen {});
+    }
+    config.lifecycle = LifecycleState::Settled;
+    CONFIG.save(deps.storage, &config)?;
+
+    let res = Response::new().add_attribute("action", "settle_event");
+    claim_released_revenue(deps, &env, &config, res)
+}
+
+/// Entry point for the curator, or the owner of the factory that created the event, to cancel event segments
+/// that have not ended, or the whole event if no segments are given.
+/// Opens a refund window for each cancelled segment, for ticket holders to claim back the share of their tickets of it.
+pub fn perform_cancel_event(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    segment_ids: Vec<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.curator && !is_factory_owner(deps.as_ref(), &config, &info.sender) {
+        return Err(ContractError::NotCuratorOrFactoryOwner {});
+    }
+    ensure_phase(
+        deps.storage,
+        env.block.time,
+        &[EventPhase::Draft, EventPhase::OnSale, EventPhase::Live],
+    )?;
+
+    let now = env.block.time;
+    let cancel_event = segment_ids.is_empty();
+    let mut segment_ids = match cancel_event {
+        true => EVENT_STAGES
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| {
+                item.as_ref().map_or(true, |(id, segment)| {
+                    segment.end > now && !CANCELLED_SEGMENTS.has(deps.storage, *id)
+                })
+            })
+            .map(|item| item.map(|(id, _)| id))
+            .collect::<StdResult<Vec<u64>>>()?,
+        false => segment_ids,
+    };
+    segment_ids.sort_unstable();
+    segment_ids.dedup();
+
+    for id in &segment_ids {
+        let segment = EVENT_STAGES
+            .may_load(deps.storage, *id)?
+            .ok_or(ContractError::IncorrectEventSegmentId {})?;
+        if segment.end <= now || CANCELLED_SEGMENTS.has(deps.storage, *id) {
+            return Err(ContractError::CannotCancelSegment { segment_id: *id });
+        }
+        cancel_segment_escrow(deps.storage, *id, now)?;
+    }
+    if cancel_event {
+        config.lifecycle = LifecycleState::Cancelled;
+        CONFIG.save(deps.storage, &config)?;
+    }
+    let deadline = now.plus_seconds(REFUND_WINDOW);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_event")
+        .add_attribute("event_cancelled", cancel_event.to_string())
+        .add_attribute("cancelled_segments", join_ids(&segment_ids))
+        .add_attribute("refund_deadline", deadline.to_string()))
+}
+
+/// whether a wallet owns the factory that created the event.
+fn is_factory_owner(deps: Deps, cfg: &Config, wallet: &Addr) -> bool {
+    let Some(factory) = cfg.factory.as_ref() else {
+        return false;
+    };
+    deps.querier
+        .query_wasm_smart::<Ownership<Addr>>(factory, &FactoryQueryMsg::Ownership {})
+        .is_ok_and(|ownership| ownership.owner.as_ref() == Some(wallet))
+}
+
+/// Entry point for ticket holders to claim refunds of cancelled event segments, while their refund windows are open.
+/// Each ticket is refunded what was paid for it net of the dev fee, in proportion to the escrow weights of its cancelled segments.
+pub fn perform_claim_cancellation_refunds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tickets: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !refund_window_open(deps.storage, env.block.time)? {
+        return Err(ContractError::NoRefundWindow {});
+    }
+    let mut refunds = vec![];
+    let mut res = Response::new().add_attribute("action", "claim_cancellation_refunds");
+
+    for ticket_addr in tickets {
+        let receipt = ticket_receipts()
+            .may_load(deps.storage, &ticket_addr)?
+            .ok_or(ContractError::NoReservedTicketsForGuest {})?;
+        if ticket_holder(deps.storage, &ticket_addr, &receipt)? != info.sender {
+            return Err(ContractError::NotTicketHolder {});
+        }
+        let gd = GUEST_DETAILS.load(deps.storage, receipt.guest_weight)?;
+        let (cancelled, total) =
+            refundable_escrow_weights(deps.storage, &gd.event_segment_access, env.block.time)?;
+        let mut ticket_refund = Uint128::zero();
+        for (segment_id, weight) in cancelled {
+            let owed = (receipt.price - receipt.dev_fee).multiply_ratio(weight, total.max(1));
+            let refunded = CANCELLATION_REFUNDS
+                .may_load(deps.storage, (&ticket_addr, segment_id))?
+                .unwrap_or_default();
+            // each pool is set aside by segment & guest type, so only falls short of what is owed by rounding
+            let key = (segment_id, receipt.guest_weight, receipt.denom.as_str());
+            let pool = REFUND_POOL.may_load(deps.storage, key)?.unwrap_or_default();
+            let refund = owed.saturating_sub(refunded).min(pool);
+            if refund.is_zero() {
+                continue;
+            }
+            REFUND_POOL.save(deps.storage, key, &(pool - refund))?;
+            CANCELLATION_REFUNDS.save(
+                deps.storage,
+                (&ticket_addr, segment_id),
+                &(refunded + refund),
+            )?;
+            ticket_refund += refund;
+        }
+        if ticket_refund.is_zero() {
+            continue;
+        }
+        add_to_coins(&mut refunds, coin(ticket_refund.u128(), &receipt.denom));
+        res = res.add_attribute("refunded", &ticket_addr);
+    }
+
+    Ok(res.add_messages(form_payment_msgs(deps.storage, &info.sender, refunds)?))
 }
 
 /// allows a wallet that was reserved a ticket from another wallet to claim their ticket,
@@ -570,6 +2812,16 @@ pub fn perform_claim_ticket_reserved_by_homie(
     info: MessageInfo,
     reserver: String,
 ) -> Result<Response, ContractError> {
+    if RESALE_LISTINGS.has(deps.storage, info.sender.as_str()) {
+        return Err(ContractError::TicketListedForResale {});
+    }
+    // a secret set by the purchaser no longer applies once the homie holds their ticket
+    if HOMIE_CHECKIN_SECRETS
+        .may_load(deps.storage, info.sender.as_str())?
+        .is_some_and(|secret| secret.set_by != info.sender)
+    {
+        HOMIE_CHECKIN_SECRETS.remove(deps.storage, info.sender.as_str());
+    }
     HOMIE_TICKETS.update(
         deps.storage,
         &reserver,
@@ -590,6 +2842,7 @@ pub fn perform_claim_ticket_reserved_by_homie(
 }
 
 /// allows a wallet has a ticket reserved to update the address to use to checkin.
+/// homie tickets can be updated by the wallet that reserved them, until claimed.
 pub fn perform_update_ticket_wallet(
     deps: DepsMut,
     info: MessageInfo,
@@ -599,77 +2852,113 @@ pub fn perform_update_ticket_wallet(
     if homies_to_update.len() > 10 {
         return Err(ContractError::TooManyHomieTickets {});
     }
-    let guest_cw420 = CONFIG.load(deps.storage)?.event_guest_contract;
+    let cfg = CONFIG.load(deps.storage)?;
+    // tickets are checked in by their membership in the guest group
+    if !cfg.guest_group.writable() {
+        return Err(ContractError::GuestGroupNotWritable {});
+    }
+    let sender = info.sender.to_string();
+    let homies = HOMIE_TICKETS
+        .may_load(deps.storage, &sender)?
+        .unwrap_or_default();
+
+    let mut moves: Vec<(String, String)> = Vec::new();
+    for homie in homies_to_update {
+        if !homies.contains(&homie.old) {
+            return Err(ContractError::NoReservedTicketsForGuest {});
+        }
+        moves.push((homie.old, homie.new));
+    }
+    if let Some(new) = new_ticket_addr {
+        moves.push((sender, new));
+    }
 
-    let querier = deps.querier;
+    let mut to_add = Vec::new();
+    let mut to_remove = Vec::new();
+    for (old, new) in moves {
+        let new = deps.api.addr_validate(&new)?.to_string();
+        let weight = move_ticket(deps.storage, &old, &new)?;
+        to_remove.push(old);
+        to_add.push(Member { addr: new, weight });
+    }
 
-    let res: MemberResponse = querier.query_wasm_smart(
-        &guest_cw420,
-        &Cw4QueryMsg::Member {
-            addr: info.sender.to_string(),
-            at_height: None,
-        },
-    )?;
+    Ok(Response::new()
+        .add_attribute("action", "update_ticket_address")
+        .add_message(form_cw420_msg(
+            cfg.event_guest_contract.to_string(),
+            to_add,
+            to_remove,
+        )?))
+}
 
-    if let Some(weight) = res.weight {
-        let sender = info.sender.to_string();
-        let mut list = vec![];
-        if let Some(homies) = HOMIE_TICKETS.may_load(deps.storage, &sender)? {
-            homies.iter().for_each(|h| {
-                if let Some(update) = homies_to_update.iter().position(|htu| &htu.old == h) {
-                    list.push(homies_to_update[update].new.clone());
-                } else {
-                    list.push(h.to_string());
-                }
-            });
-        }
+/// moves a ticket, along with its receipt, attendance records & checkins, to a new ticket address.
+/// returns the guest weight of the ticket.
+fn move_ticket(
+    storage: &mut dyn Storage,
+    old: &String,
+    new: &String,
+) -> Result<u64, ContractError> {
+    let receipt = ticket_receipts()
+        .may_load(storage, old)?
+        .ok_or(ContractError::NoReservedTicketsForGuest {})?;
+    if RESALE_LISTINGS.has(storage, old) {
+        return Err(ContractError::TicketListedForResale {});
+    }
+    if ticket_receipts().has(storage, new) {
+        return Err(ContractError::TicketAlreadyPurchased {});
+    }
+    ticket_receipts().remove(storage, old)?;
+    HOMIE_CHECKIN_SECRETS.remove(storage, old);
+    ticket_receipts().save(storage, new, &receipt)?;
+    let refunds = CANCELLATION_REFUNDS
+        .prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (segment_id, refunded) in refunds {
+        CANCELLATION_REFUNDS.remove(storage, (old, segment_id));
+        CANCELLATION_REFUNDS.save(storage, (new, segment_id), &refunded)?;
+    }
 
-        let mut to_add = Vec::new();
-        let mut to_remove = Vec::new();
-
-        // remap any homie tickets to new address
-        if list.len() > 0 {
-            let mut ticket_addr = sender.clone();
-            if let Some(new) = new_ticket_addr {
-                HOMIE_TICKETS.remove(deps.storage, &ticket_addr);
-                ticket_addr = new;
-                to_remove.push(sender);
-                to_add.push(Member {
-                    addr: ticket_addr.clone(),
-                    weight,
-                });
-            }
-            HOMIE_TICKETS.save(deps.storage, &ticket_addr, &list)?;
-        }
+    let records = attendance_records()
+        .prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, record) in records {
+        attendance_records().remove(storage, (old, id))?;
+        attendance_records().save(
+            storage,
+            (new, id),
+            &AttendanceRecord {
+                ticket_addr: new.to_string(),
+                ..record
+            },
+        )?;
+    }
+    let checkins = checkin_log()
+        .sub_prefix(old)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((id, seq), entry) in checkins {
+        checkin_log().remove(storage, (old, id, seq))?;
+        checkin_log().save(
+            storage,
+            (new, id, seq),
+            &CheckInLogEntry {
+                ticket_addr: new.to_string(),
+                ..entry
+            },
+        )?;
+    }
 
-        // get weights for each addr
-        for homie in homies_to_update {
-            let res: MemberResponse = querier.query_wasm_smart(
-                &guest_cw420,
-                &Cw4QueryMsg::Member {
-                    addr: info.sender.to_string(),
-                    at_height: None,
-                },
-            )?;
-            if let Some(weight) = res.weight {
-                to_add.push(Member {
-                    addr: homie.new,
-                    weight,
-                });
-                to_remove.push(homie.old);
-            }
+    // keep unclaimed homie tickets of the purchaser pointing at the ticket
+    let purchaser = receipt.purchaser.to_string();
+    if let Some(mut homies) = HOMIE_TICKETS.may_load(storage, &purchaser)? {
+        if let Some(pos) = homies.iter().position(|h| h == old) {
+            homies[pos] = new.clone();
+            HOMIE_TICKETS.save(storage, &purchaser, &homies)?;
         }
-
-        Ok(
-            Response::new().add_message(form_cw420_msg(
-                guest_cw420.to_string(),
-                to_add,
-                to_remove,
-            )?),
-        )
-    } else {
-        return Err(ContractError::NoReservedTicketsForGuest {});
     }
+    Ok(receipt.guest_weight)
 }
 
 fn form_cw420_msg(
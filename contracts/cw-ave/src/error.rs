@@ -1,6 +1,8 @@
-use cosmwasm_std::{Instantiate2AddressError, StdError, VerificationError};
+use cosmwasm_std::{Coin, Instantiate2AddressError, StdError, Timestamp, VerificationError};
 use thiserror::Error;
 
+use crate::state::EventPhase;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
@@ -36,6 +38,99 @@ pub enum ContractError {
     #[error("event stage start must come before event stage end.")]
     InvalidEventDates {},
 
+    #[error("event stage escrow weight must be greater than zero.")]
+    InvalidEscrowWeight {},
+
+    #[error("pubkey must be a protobuf Any encoded public key of the signature scheme.")]
+    InvalidPubkey {},
+
+    #[error("the pubkey that signed the checkin does not belong to the ticket address.")]
+    PubkeyTicketAddrMismatch {},
+
+    #[error("checkin signature was made for a different event contract.")]
+    CheckinWrongEventContract {},
+
+    #[error("checkin signature was made for a different usher.")]
+    CheckinWrongUsher {},
+
+    #[error("checkin signature has expired.")]
+    CheckinSignatureExpired {},
+
+    #[error("checkin signature cannot be valid for more than {max_seconds} seconds.")]
+    CheckinSignatureExpiryTooLong { max_seconds: u64 },
+
+    #[error("checkin signature nonce has already been used.")]
+    CheckinNonceUsed {},
+
+    #[error("none of the tickets could be checked in.")]
+    NoTicketsCheckedIn {},
+
+    #[error("cannot checkin more than {max} guests at once.")]
+    TooManyCheckins { max: usize },
+
+    #[error("checkin {index} of the batch failed: {reason}")]
+    BatchCheckinFailed { index: usize, reason: String },
+
+    #[error("too early to checkin to event segment {segment_id}, checkin opens at {opens_at}.")]
+    CheckinTooEarly {
+        segment_id: u64,
+        opens_at: Timestamp,
+    },
+
+    #[error("too late to checkin to event segment {segment_id}, checkin closed at {closed_at}.")]
+    CheckinTooLate {
+        segment_id: u64,
+        closed_at: Timestamp,
+    },
+
+    #[error("only the event curator can perform this action.")]
+    NotEventCurator {},
+
+    #[error("webauthn client data must be a webauthn.get assertion of the checkin sign doc.")]
+    InvalidWebAuthnClientData {},
+
+    #[error("webauthn authenticator data must be for this event's relying party, with the user present.")]
+    InvalidWebAuthnAuthenticatorData {},
+
+    #[error("this event does not accept webauthn checkins.")]
+    WebAuthnNotEnabled {},
+
+    #[error("this ticket has no homie checkin secret set.")]
+    NoHomieCheckinSecret {},
+
+    #[error("incorrect homie checkin secret.")]
+    IncorrectHomieCheckinSecret {},
+
+    #[error("homie checkin secret hash must be a 32 byte sha256 hash.")]
+    InvalidHomieCheckinSecretHash {},
+
+    #[error("this ticket is listed for resale.")]
+    TicketListedForResale {},
+
+    #[error("this ticket is not listed for resale.")]
+    NotListedForResale {},
+
+    #[error("only the holder of this ticket can do this.")]
+    NotTicketHolder {},
+
+    #[error("resale price must be in a denom accepted for this guest type.")]
+    InvalidResalePrice {},
+
+    #[error("resale price is above the cap of {max}.")]
+    ResalePriceAboveCap { max: Coin },
+
+    #[error("funds sent do not cover the resale price.")]
+    InsufficientResalePayment {},
+
+    #[error("resale royalty cannot be more than 10000 basis points.")]
+    InvalidResaleSettings {},
+
+    #[error("this ticket address is not on the waitlist.")]
+    NotOnWaitlist {},
+
+    #[error("this cw20 token is not accepted as ticket payment.")]
+    UnsupportedCw20Token {},
+
     #[error("IncorrectCheckinSignature")]
     IncorrectCheckinSignature {},
 
@@ -71,4 +166,89 @@ pub enum ContractError {
 
     #[error("this guest is not allowed to checkin for this specific event segment.")]
     IncorrectEventSegmentId {},
+
+    #[error("a ticket has already been purchased for this ticket address.")]
+    TicketAlreadyPurchased {},
+
+    #[error("only the ticket purchaser or event curator can refund this ticket.")]
+    NotTicketPurchaser {},
+
+    #[error("tickets cannot be refunded once an event segment they grant access to has ended.")]
+    RefundWindowClosed {},
+
+    #[error("provide either usher members or an existing cw4 group, that must be queryable as a cw4 group.")]
+    InvalidEventGroup {},
+
+    #[error("this event cannot update the members of its guest group.")]
+    GuestGroupNotWritable {},
+
+    #[error("this event cannot update the members of its usher group.")]
+    UsherGroupNotWritable {},
+
+    #[error("this wallet is already an usher for this event.")]
+    UsherAlreadyExists {},
+
+    #[error("this wallet is not an usher for this event.")]
+    UsherNotFound {},
+
+    #[error("this usher cannot checkin guests to these event segments, or at this gate.")]
+    OutsideUsherScope {},
+
+    #[error("only supervisors can override checkin errors.")]
+    NotASupervisor {},
+
+    #[error("gate labels must be set, and at most 128 characters.")]
+    InvalidGateLabel {},
+
+    #[error("this ticket address is not a member of the event guest group with the weight of its guest type.")]
+    NotInGuestGroup {},
+
+    #[error("this guest is not inside this event segment.")]
+    GuestNotCheckedIn {},
+
+    #[error("this guest type cannot re-enter an event segment after checking out, or has no re-entries left.")]
+    ReentryNotAllowed {},
+
+    #[error("event segment {segment_id} is at capacity.")]
+    SegmentAtCapacity { segment_id: u64 },
+
+    #[error("ticket limit cannot be less than the {sold} tickets already sold.")]
+    TicketLimitBelowSold { sold: u32 },
+
+    #[error(
+        "event segments can only be rescheduled before they start, to a time that has not passed."
+    )]
+    CannotRescheduleSegment {},
+
+    #[error("this action is not allowed while the event is {phase}.")]
+    WrongEventPhase { phase: EventPhase },
+
+    #[error("only the event curator, or the owner of the factory that created the event, can cancel it.")]
+    NotCuratorOrFactoryOwner {},
+
+    #[error("event segment {segment_id} has already ended or been cancelled.")]
+    CannotCancelSegment { segment_id: u64 },
+
+    #[error("event segment {segment_id} was cancelled.")]
+    SegmentCancelled { segment_id: u64 },
+
+    #[error(
+        "event segments of this ticket were cancelled, claim its cancellation refund instead."
+    )]
+    TicketSegmentsCancelled {},
+
+    #[error("tickets of guest type {guest_weight} are no longer sold, as event segments they grant access to were cancelled.")]
+    GuestTypeSegmentsCancelled { guest_weight: u64 },
+
+    #[error("ticket revenue cannot be claimed or settled while cancellation refunds can still be claimed.")]
+    RefundWindowOpen {},
+
+    #[error("there are no cancellation refunds to claim, or their refund window has closed.")]
+    NoRefundWindow {},
+
+    #[error("revenue splits need valid payees, basis points adding up to at most 10000, and amounts per ticket in denoms accepted for their guest type.")]
+    InvalidRevenueSplits {},
+
+    #[error("this wallet is not a payee of the revenue splits of this event.")]
+    NotARevenuePayee {},
 }
@@ -0,0 +1,351 @@
+use cosmwasm_std::{Coin, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Map, PrimaryKey};
+
+use crate::contract::MAX_BPS;
+use crate::msg::{EscrowStatusResponse, PayeeRevenueRes};
+use crate::state::{
+    EventSegmentAccessType, GuestDetails, RevenueShare, RevenueSplit, CANCELLED_SEGMENTS,
+    CLAIMED_REVENUE, CONFIG, ESCROWED_REVENUE, EVENT_STAGES, GUEST_DETAILS, REFUND_POOL,
+    TICKETS_SOLD, WITHDRAWN_REVENUE_SHARES,
+};
+
+/// escrow weight used for event segments that do not define one.
+pub const DEFAULT_ESCROW_WEIGHT: u64 = 1;
+/// seconds ticket holders have to claim the refund of a cancelled segment, from its cancellation.
+pub const REFUND_WINDOW: u64 = 30 * 24 * 60 * 60;
+
+/// adds ticket revenue (net of the dev fee) to the escrow of a guest type.
+/// guest types with cancelled segments are no longer sold, so none of it is refundable.
+pub fn escrow_ticket_revenue(
+    storage: &mut dyn Storage,
+    gd: &GuestDetails,
+    revenue: &Coin,
+) -> StdResult<()> {
+    add_to_escrow(
+        storage,
+        ESCROWED_REVENUE,
+        (gd.guest_weight, &revenue.denom),
+        revenue.amount,
+    )
+}
+
+fn add_to_escrow<'a, K: PrimaryKey<'a>>(
+    storage: &mut dyn Storage,
+    map: Map<K, Uint128>,
+    key: K,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    map.update(storage, key, |escrowed| -> StdResult<_> {
+        Ok(escrowed.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+/// cancels an event segment, moving its share of the escrowed revenue of every guest type
+/// with access to it into its refund pool, & opening its refund window.
+/// the released & locked escrow of these guest types is unchanged, as the segment no longer counts towards their weights.
+pub fn cancel_segment_escrow(
+    storage: &mut dyn Storage,
+    segment_id: u64,
+    now: Timestamp,
+) -> StdResult<()> {
+    let segment = EVENT_STAGES.load(storage, segment_id)?;
+    let weight = segment.escrow_weight.unwrap_or(DEFAULT_ESCROW_WEIGHT) as u128;
+    let guest_types = GUEST_DETAILS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (guest_weight, gd) in guest_types {
+        if !gd.event_segment_access.segment_ids().contains(&segment_id) {
+            continue;
+        }
+        let (_, remaining) = released_escrow_weights(storage, &gd.event_segment_access, now)?;
+        let escrowed = ESCROWED_REVENUE
+            .prefix(guest_weight)
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (denom, amount) in escrowed {
+            let refundable = amount.multiply_ratio(weight, remaining);
+            ESCROWED_REVENUE.save(storage, (guest_weight, &denom), &(amount - refundable))?;
+            add_to_escrow(
+                storage,
+                REFUND_POOL,
+                (segment_id, guest_weight, &denom),
+                refundable,
+            )?;
+        }
+    }
+    CANCELLED_SEGMENTS.save(storage, segment_id, &now.plus_seconds(REFUND_WINDOW))
+}
+
+/// returns the (cancelled, total) escrow weights of the segments an access type grants entry to.
+pub fn cancelled_escrow_weights(
+    storage: &dyn Storage,
+    access: &EventSegmentAccessType,
+) -> StdResult<(u128, u128)> {
+    let mut cancelled = 0u128;
+    let mut total = 0u128;
+    for id in access.segment_ids() {
+        let segment = EVENT_STAGES.load(storage, id)?;
+        let weight = segment.escrow_weight.unwrap_or(DEFAULT_ESCROW_WEIGHT) as u128;
+        total += weight;
+        if CANCELLED_SEGMENTS.has(storage, id) {
+            cancelled += weight;
+        }
+    }
+    Ok((cancelled, total))
+}
+
+/// returns the escrow weights of the cancelled segments an access type grants entry to whose refund window is open,
+/// by segment id, along with the total escrow weight of its segments.
+pub fn refundable_escrow_weights(
+    storage: &dyn Storage,
+    access: &EventSegmentAccessType,
+    now: Timestamp,
+) -> StdResult<(Vec<(u64, u128)>, u128)> {
+    let mut refundable = vec![];
+    let mut total = 0u128;
+    for id in access.segment_ids() {
+        let segment = EVENT_STAGES.load(storage, id)?;
+        let weight = segment.escrow_weight.unwrap_or(DEFAULT_ESCROW_WEIGHT) as u128;
+        total += weight;
+        if CANCELLED_SEGMENTS
+            .may_load(storage, id)?
+            .is_some_and(|deadline| now < deadline)
+        {
+            refundable.push((id, weight));
+        }
+    }
+    Ok((refundable, total))
+}
+
+/// whether ticket holders can still claim refunds of any cancelled segment.
+pub fn refund_window_open(storage: &dyn Storage, now: Timestamp) -> StdResult<bool> {
+    Ok(refund_deadline(storage)?.is_some_and(|deadline| now < deadline))
+}
+
+/// when the last refund window of cancelled segments closes, if any segment was cancelled.
+pub fn refund_deadline(storage: &dyn Storage) -> StdResult<Option<Timestamp>> {
+    CANCELLED_SEGMENTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, deadline)| deadline))
+        .try_fold(None, |latest: Option<Timestamp>, deadline| {
+            Ok(latest.max(Some(deadline?)))
+        })
+}
+
+/// empties the refund pools of segments whose refund window closed, returning what was left unclaimed by denom.
+pub fn take_expired_refunds(storage: &mut dyn Storage, now: Timestamp) -> StdResult<Vec<Coin>> {
+    let pool = REFUND_POOL
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut expired = vec![];
+    for ((segment_id, guest_weight, denom), amount) in pool {
+        if now < CANCELLED_SEGMENTS.load(storage, segment_id)? {
+            continue;
+        }
+        REFUND_POOL.remove(storage, (segment_id, guest_weight, &denom));
+        add_to_denom(&mut expired, &denom, amount);
+    }
+    expired.retain(|c: &Coin| !c.amount.is_zero());
+    Ok(expired)
+}
+
+/// returns the (released, total) escrow weights of the segments an access type grants entry to.
+/// a segment releases its weight once its end has passed. Cancelled segments are refunded instead, and left out of both.
+pub fn released_escrow_weights(
+    storage: &dyn Storage,
+    access: &EventSegmentAccessType,
+    now: Timestamp,
+) -> StdResult<(u128, u128)> {
+    let mut released = 0u128;
+    let mut total = 0u128;
+    for id in access.segment_ids() {
+        if CANCELLED_SEGMENTS.has(storage, id) {
+            continue;
+        }
+        let segment = EVENT_STAGES.load(storage, id)?;
+        let weight = segment.escrow_weight.unwrap_or(DEFAULT_ESCROW_WEIGHT) as u128;
+        total += weight;
+        if segment.end <= now {
+            released += weight;
+        }
+    }
+    Ok((released, total))
+}
+
+/// amount of an escrowed balance that has been released, given its (released, total) weights.
+pub fn released_amount(escrowed: Uint128, (released, total): (u128, u128)) -> Uint128 {
+    if total == 0 {
+        return escrowed;
+    }
+    escrowed.multiply_ratio(released, total)
+}
+
+/// summarizes escrowed ticket revenue by denom: what the curator may claim now,
+/// what is still locked until future segments end, what was already claimed,
+/// and what is set aside for cancellation refunds.
+pub fn escrow_status(storage: &dyn Storage, now: Timestamp) -> StdResult<EscrowStatusResponse> {
+    let mut locked = vec![];
+
+    for item in ESCROWED_REVENUE.range(storage, None, None, Order::Ascending) {
+        let ((guest_weight, denom), escrowed) = item?;
+        let gd = GUEST_DETAILS.load(storage, guest_weight)?;
+        let weights = released_escrow_weights(storage, &gd.event_segment_access, now)?;
+        add_to_denom(
+            &mut locked,
+            &denom,
+            escrowed - released_amount(escrowed, weights),
+        );
+    }
+    // revenue split payees withdraw their shares themselves
+    let splits = CONFIG.load(storage)?.revenue_splits;
+    let (released, _) = released_shares(storage, &splits, now)?;
+
+    let claimed = CLAIMED_REVENUE
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<Coin>>>()?;
+
+    let mut claimable: Vec<Coin> = released
+        .into_iter()
+        .map(|mut c| {
+            if let Some(done) = claimed.iter().find(|d| d.denom == c.denom) {
+                c.amount = c.amount.saturating_sub(done.amount);
+            }
+            c
+        })
+        .collect();
+
+    // unclaimed refunds are released to the curator once the refund window of their segment closes
+    let mut refundable = vec![];
+    for item in REFUND_POOL.range(storage, None, None, Order::Ascending) {
+        let ((segment_id, _, denom), amount) = item?;
+        match now < CANCELLED_SEGMENTS.load(storage, segment_id)? {
+            true => add_to_denom(&mut refundable, &denom, amount),
+            false => add_to_denom(&mut claimable, &denom, amount),
+        }
+    }
+
+    claimable.retain(|c| !c.amount.is_zero());
+    locked.retain(|c| !c.amount.is_zero());
+    refundable.retain(|c| !c.amount.is_zero());
+
+    Ok(EscrowStatusResponse {
+        claimable,
+        locked,
+        claimed,
+        refundable,
+        refund_deadline: refund_deadline(storage)?,
+    })
+}
+
+/// released ticket revenue by denom, split between the curator & each revenue split, in the order of the splits.
+/// shares paid per ticket come first, capped at what was released of their guest type,
+/// then basis point shares of the rest, leaving the curator what is left.
+pub fn released_shares(
+    storage: &dyn Storage,
+    splits: &[RevenueSplit],
+    now: Timestamp,
+) -> StdResult<(Vec<Coin>, Vec<Vec<Coin>>)> {
+    let mut curator = vec![];
+    let mut shares = vec![vec![]; splits.len()];
+
+    for item in ESCROWED_REVENUE.range(storage, None, None, Order::Ascending) {
+        let ((guest_weight, denom), escrowed) = item?;
+        let gd = GUEST_DETAILS.load(storage, guest_weight)?;
+        let weights = released_escrow_weights(storage, &gd.event_segment_access, now)?;
+        let released = released_amount(escrowed, weights);
+
+        // shares per ticket are released with the segments of the guest type, and not paid for cancelled segments
+        let per_ticket: Vec<Uint128> = splits
+            .iter()
+            .map(|split| match &split.share {
+                RevenueShare::PerTicket {
+                    guest_weight: weight,
+                    amount,
+                } if *weight == guest_weight => amount
+                    .iter()
+                    .find(|c| c.denom == denom)
+                    .map_or(Uint128::zero(), |c| c.amount),
+                _ => Uint128::zero(),
+            })
+            .collect();
+        let per_ticket_total: Uint128 = per_ticket.iter().sum();
+        let sold = TICKETS_SOLD
+            .may_load(storage, (guest_weight, &denom))?
+            .unwrap_or_default();
+        let (_, total) = cancelled_escrow_weights(storage, &gd.event_segment_access)?;
+        let fixed = per_ticket_total
+            .checked_mul(Uint128::from(sold))?
+            .multiply_ratio(weights.0, total.max(1))
+            .min(released);
+
+        let mut rest = released;
+        for (i, amount) in per_ticket.iter().enumerate() {
+            if amount.is_zero() {
+                continue;
+            }
+            let share = fixed.multiply_ratio(*amount, per_ticket_total);
+            rest -= share;
+            add_to_denom(&mut shares[i], &denom, share);
+        }
+        let after_per_ticket = rest;
+        for (i, split) in splits.iter().enumerate() {
+            if let RevenueShare::Bps { bps } = split.share {
+                let share = after_per_ticket.multiply_ratio(bps, MAX_BPS);
+                rest -= share;
+                add_to_denom(&mut shares[i], &denom, share);
+            }
+        }
+        add_to_denom(&mut curator, &denom, rest);
+    }
+    Ok((curator, shares))
+}
+
+/// revenue accrued & withdrawn by each revenue split payee, listing payees of several splits once.
+pub fn payee_revenue(
+    storage: &dyn Storage,
+    splits: &[RevenueSplit],
+    now: Timestamp,
+) -> StdResult<Vec<PayeeRevenueRes>> {
+    let (_, shares) = released_shares(storage, splits, now)?;
+    let mut payees: Vec<PayeeRevenueRes> = vec![];
+    for (split, accrued) in splits.iter().zip(shares) {
+        let i = match payees.iter().position(|p| p.payee == split.payee) {
+            Some(i) => i,
+            None => {
+                let withdrawn = WITHDRAWN_REVENUE_SHARES
+                    .prefix(&split.payee)
+                    .range(storage, None, None, Order::Ascending)
+                    .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+                    .collect::<StdResult<Vec<Coin>>>()?;
+                payees.push(PayeeRevenueRes {
+                    payee: split.payee.clone(),
+                    accrued: vec![],
+                    withdrawn,
+                });
+                payees.len() - 1
+            }
+        };
+        for c in accrued {
+            add_to_denom(&mut payees[i].accrued, &c.denom, c.amount);
+        }
+    }
+    for payee in payees.iter_mut() {
+        payee.accrued.retain(|c| !c.amount.is_zero());
+    }
+    Ok(payees)
+}
+
+fn add_to_denom(coins: &mut Vec<Coin>, denom: &str, amount: Uint128) {
+    match coins.iter_mut().find(|c| c.denom == denom) {
+        Some(c) => c.amount += amount,
+        None => coins.push(Coin {
+            denom: denom.to_string(),
+            amount,
+        }),
+    }
+}
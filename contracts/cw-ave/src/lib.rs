@@ -1,6 +1,9 @@
+pub mod checkin;
 pub mod contract;
 mod error;
+pub mod escrow;
 pub mod helpers;
+pub mod lifecycle;
 pub mod msg;
 pub mod state;
 
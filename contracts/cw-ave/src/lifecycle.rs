@@ -0,0 +1,63 @@
+use cosmwasm_std::{Order, StdResult, Storage, Timestamp};
+
+use crate::msg::StatusResponse;
+use crate::state::{EventPhase, LifecycleState, CONFIG, EVENT_STAGES};
+use crate::ContractError;
+
+/// start of the first event segment & end of the last, if the event has any segments.
+/// segments are ordered by id & do not overlap.
+fn event_window(storage: &dyn Storage) -> StdResult<Option<(Timestamp, Timestamp)>> {
+    let first = EVENT_STAGES
+        .range(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+    let last = EVENT_STAGES
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    Ok(first.zip(last).map(|((_, f), (_, l))| (f.start, l.end)))
+}
+
+/// current phase of the event, with the next phase it moves to.
+/// transitions driven by block time report when they happen, those left to the curator do not.
+pub fn event_status(storage: &dyn Storage, now: Timestamp) -> StdResult<StatusResponse> {
+    let (phase, next_phase, next_transition) = match CONFIG.load(storage)?.lifecycle {
+        LifecycleState::Draft => (EventPhase::Draft, Some(EventPhase::OnSale), None),
+        LifecycleState::Cancelled => (EventPhase::Cancelled, None, None),
+        LifecycleState::Settled => (EventPhase::Settled, None, None),
+        LifecycleState::Published => match event_window(storage)? {
+            Some((start, _)) if now < start => {
+                (EventPhase::OnSale, Some(EventPhase::Live), Some(start))
+            }
+            Some((_, end)) if now < end => (EventPhase::Live, Some(EventPhase::Ended), Some(end)),
+            Some(_) => (EventPhase::Ended, Some(EventPhase::Settled), None),
+            None => (EventPhase::OnSale, None, None),
+        },
+    };
+    Ok(StatusResponse {
+        phase,
+        next_phase,
+        next_transition,
+    })
+}
+
+/// errors unless the event is currently in one of the allowed phases, returning the current phase.
+pub fn ensure_phase(
+    storage: &dyn Storage,
+    now: Timestamp,
+    allowed: &[EventPhase],
+) -> Result<EventPhase, ContractError> {
+    let phase = event_status(storage, now)?.phase;
+    if !allowed.contains(&phase) {
+        return Err(ContractError::WrongEventPhase { phase });
+    }
+    Ok(phase)
+}
+
+/// phases tickets can be purchased, refunded & resold in.
+pub const SALE_PHASES: &[EventPhase] = &[EventPhase::OnSale, EventPhase::Live];
+/// phases the curator can edit the event in.
+pub const EDIT_PHASES: &[EventPhase] = &[EventPhase::Draft, EventPhase::OnSale, EventPhase::Live];
+/// phases guests can be checked in, and ticket revenue claimed in.
+pub const ATTENDANCE_PHASES: &[EventPhase] =
+    &[EventPhase::OnSale, EventPhase::Live, EventPhase::Ended];
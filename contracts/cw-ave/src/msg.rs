@@ -1,9 +1,13 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp};
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
 use cw4::Member;
 
 use crate::state::{
-    CheckInDetails, Config, EventSegment, GuestDetails, RegisteringGuest, ReplaceHomieTicket,
-    TicketPaymentOption,
+    AttendanceRecord, BatchCheckInMode, CheckInDetails, CheckInLogEntry, Config, EventPhase,
+    EventSegment, GuestDetails, HomieCheckinSecret, PoapSettings, RegisteringGuest,
+    ReplaceHomieTicket, ResaleListing, ResaleSettings, RevenueSplit, TicketPaymentOption,
+    TicketReceipt, UsherScope, WaitlistEntry,
 };
 
 #[cw_serde]
@@ -14,38 +18,182 @@ pub struct InstantiateMsg {
     pub title: String,
     /// description of avEvent for recordkeeping
     pub description: String,
-    /// list of admin keys able to checkin guests
+    /// list of admin keys able to checkin guests. Must be empty if `existing_usher_group` is set
     pub usher_admins: Vec<Member>,
+    /// existing cw4 group of ushers to use, instead of instantiating a cw420 group
+    pub existing_usher_group: Option<String>,
+    /// lowest usher weight marking supervisors, who are not restricted by usher scopes & can override checkin windows.
+    /// There are no supervisors if not set
+    pub usher_supervisor_weight: Option<u64>,
+    /// existing cw4 group of guests to use, instead of instantiating a cw420 group.
+    /// If this contract is not its admin, tickets can only be purchased for its members, with the weight of their guest type.
+    pub existing_guest_group: Option<String>,
     /// details of each type of guest attendees can participate as
     pub guest_details: Vec<GuestDetails>,
     /// code-id of cw420 contract
     pub cw420: u64,
     /// timeline of events segments
     pub event_timeline: Vec<EventSegment>,
+    /// royalty & price cap of ticket resales. Resales are uncapped, without royalty if not set
+    pub resale: Option<ResaleSettings>,
+    /// proof-of-attendance collection minting a token for each event segment a guest checks in to.
+    /// No tokens are minted if not set
+    pub poap: Option<PoapSettings>,
+    /// create the event as a draft, with tickets not on sale until the curator opens sales
+    #[serde(default)]
+    pub draft: bool,
+    /// shares of ticket revenue paid to payees other than the curator, who gets what is left
+    #[serde(default)]
+    pub revenue_splits: Vec<RevenueSplit>,
+    /// WebAuthn relying party id (domain) passkey checkins must be made for.
+    /// Passkey checkins are refused if not set
+    #[serde(default)]
+    pub webauthn_rp_id: Option<String>,
 }
 
 #[cw_serde]
 #[derive(cw_orch::ExecuteFns)]
 pub enum ExecuteMsg {
-    // /// Cw20 Entry Point
-    // Receive(Cw20ReceiveMsg),
+    /// Cw20 Entry Point, used to purchase tickets with cw20 tokens
+    Receive(Cw20ReceiveMsg),
     PurchaseTickets {
         guests: Vec<RegisteringGuest>,
     },
     CheckInGuest {
         checkin: CheckInDetails,
     },
+    /// Edit the event, validated as when it was created. Callable by the event curator.
+    /// Changes that would hurt ticket holders are refused, such as fewer tickets than sold or moving segments that started.
+    UpdateEvent {
+        title: Option<String>,
+        description: Option<String>,
+        /// new guest types, with weights not used by existing guest types
+        add_guest_types: Vec<GuestDetails>,
+        /// new ticket limits & prices of existing guest types
+        update_guest_types: Vec<GuestTypeUpdate>,
+        /// new times of event segments that have not started yet
+        reschedule_segments: Vec<SegmentSchedule>,
+    },
+    /// Set how long before & after an event segment guests can check in. Callable by the event curator.
+    UpdateCheckinGracePeriods {
+        segment_id: u64,
+        /// seconds before the segment start that checkin opens
+        early_checkin_grace: Option<u64>,
+        /// seconds after the segment end that checkin closes
+        late_checkin_grace: Option<u64>,
+    },
+    /// Checkin many guests in a single transaction, as event usher.
+    BatchCheckInGuests {
+        checkins: Vec<CheckInDetails>,
+        /// whether an invalid checkin fails the whole batch, or is skipped
+        mode: BatchCheckInMode,
+    },
+    /// Refund tickets that have not been checked into yet, minus the dev fee.
+    /// Callable by the wallet that purchased the tickets, or the event curator.
     RefundUnconfirmedTickets {
+        /// ticket addresses to refund
         guests: Vec<String>,
     },
+    /// Commit to a secret a homie can share with an usher to check in with a ticket, without the ticket key signing.
+    /// Callable by the ticket holder, replacing any secret already set. Each secret can be used once.
+    SetHomieCheckinSecret {
+        ticket_addr: String,
+        /// sha256 hash of the secret
+        secret_hash: Binary,
+    },
+    /// Remove the homie checkin secret of a ticket. Callable by the ticket holder.
+    RevokeHomieCheckinSecret {
+        ticket_addr: String,
+    },
+    /// Checkin a ticket with the secret committed to by its holder, as event usher.
+    CheckInWithHomieSecret {
+        ticket_addr: String,
+        secret: String,
+        event_segment_ids: Vec<u64>,
+        /// gate the guest is checking in at
+        gate: Option<String>,
+    },
+    /// Check a guest out of event segments they are inside, as event usher.
+    /// Guests can only check back in if their guest type allows re-entry.
+    CheckOutGuest {
+        ticket_addr: String,
+        event_segment_ids: Vec<u64>,
+        /// gate the guest is checking out at
+        gate: Option<String>,
+    },
+    /// Check out of event segments the ticket is inside. Callable by the ticket address.
+    CheckOut {
+        event_segment_ids: Vec<u64>,
+    },
+    /// List a ticket for resale. Callable by the ticket holder, or the purchaser of a ticket still reserved for a homie.
+    /// The ticket cannot check in while listed.
+    ListTicketForResale {
+        ticket_addr: String,
+        /// price in any denom accepted for the guest type of the ticket, capped relative to what was paid for it
+        price: Coin,
+    },
+    /// Remove a resale listing. Callable by the seller.
+    CancelResaleListing {
+        ticket_addr: String,
+    },
+    /// Buy a ticket listed for resale, with the price sent as funds.
+    /// The ticket moves to `new_ticket_addr` in the guest list & the seller is paid.
+    BuyResaleTicket {
+        ticket_addr: String,
+        new_ticket_addr: String,
+    },
+    /// Leave the waitlist of a sold out guest type, returning the full price paid.
+    /// Callable by the wallet that paid for the waitlisted tickets.
+    WithdrawFromWaitlist {
+        /// ticket addresses to withdraw
+        tickets: Vec<String>,
+    },
+    /// Add ushers to the event usher group. Callable by the event curator.
+    AddUshers {
+        ushers: Vec<Member>,
+    },
+    /// Remove ushers from the event usher group. Callable by the event curator.
+    RemoveUshers {
+        ushers: Vec<String>,
+    },
+    /// Update the weights of existing ushers. Callable by the event curator.
+    UpdateUsherWeights {
+        ushers: Vec<Member>,
+    },
+    /// Restrict an usher to checking in guests to specific segments, or at specific gates.
+    /// Removes any restriction if `scope` is not set. Callable by the event curator.
+    SetUsherScope {
+        usher: String,
+        scope: Option<UsherScope>,
+    },
+    /// Claim ticket revenue released from escrow by event segments that have ended. Curator only.
     ClaimTicketPayments {},
+    /// Put a draft event on sale. Curator only.
+    OpenSales {},
+    /// Cancel event segments that have not ended, or the whole event if no segments are given.
+    /// Ticket holders can claim back the share of their ticket price of cancelled segments until the refund window of each segment closes.
+    /// Callable by the event curator, or the owner of the factory that created the event.
+    CancelEvent {
+        segment_ids: Vec<u64>,
+    },
+    /// Claim refunds of cancelled event segments for tickets held by the sender.
+    ClaimCancellationRefunds {
+        tickets: Vec<String>,
+    },
+    /// Withdraw the revenue split share of the sender accrued in a denom. Revenue split payees only.
+    WithdrawRevenueShare {
+        denom: String,
+    },
+    /// Settle an event once its last segment ended, paying out all unclaimed ticket revenue.
+    /// Settled events no longer accept checkins or claims. Curator only.
+    SettleEvent {},
     /// Claim your ticket that your homie has purchased for you.
     /// They will not be able to check you in once you claimed your ticket
     ClaimTicketReservedByHomie {
         homie_addr: String,
     },
     UpdateTicketAddress {
-        /// Optional value to update your own. Left empty 
+        /// Optional value to update your own. Left empty
         new_ticket_addr: Option<String>,
         replace_homies_ticket: Vec<ReplaceHomieTicket>,
     },
@@ -53,7 +201,14 @@ pub enum ExecuteMsg {
 
 #[cw_serde]
 pub enum ReceiveMsg {
+    /// Purchase tickets with the cw20 tokens sent.
+    /// `payment_asset` of each ticket is the cw20 token contract address.
     PurchaseTickets { guests: Vec<RegisteringGuest> },
+    /// Buy a ticket listed for resale with the cw20 tokens sent.
+    BuyResaleTicket {
+        ticket_addr: String,
+        new_ticket_addr: String,
+    },
 }
 
 #[cw_serde]
@@ -74,15 +229,128 @@ pub enum QueryMsg {
     /// returns whether or not a guest has checked in for a specific segment of this event
     #[returns(bool)]
     GuestAttendanceStatus { guest: String, event_stage_id: u64 },
-    #[returns(Vec<bool>)]
-    /// Checkin status for a single guest, for all stages of this event
+    #[returns(Vec<(u64, bool)>)]
+    /// Checkin status for a single guest, by event stage id, for every stage their ticket grants access to
     GuestAttendanceStatusAll { guest: String },
+    /// Attendance records of a single guest, with who checked them in to each event stage, when & where
+    #[returns(Vec<AttendanceRecord>)]
+    GuestAttendanceRecords { guest: String },
+    /// Attendance records of the tickets checked in to an event segment
+    #[returns(Vec<AttendanceRecord>)]
+    SegmentAttendees {
+        segment_id: u64,
+        /// ticket address to start after
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Checkins made by an usher, including re-entries
+    #[returns(Vec<CheckInLogEntry>)]
+    UsherCheckins {
+        usher: String,
+        /// (ticket address, event segment id, seq) to start after
+        start_after: Option<(String, u64, u32)>,
+        limit: Option<u32>,
+    },
+    /// Every checkin of a single guest, by event segment, including re-entries
+    #[returns(Vec<CheckInLogEntry>)]
+    GuestCheckins {
+        guest: String,
+        /// (event segment id, seq) to start after
+        start_after: Option<(u64, u32)>,
+        limit: Option<u32>,
+    },
+    /// Guests currently inside each event segment, with its capacity
+    #[returns(Vec<OccupancyRes>)]
+    Occupancy {},
     /// All payment options accepted for a given ticket type
     #[returns(TicketPaymentOption)]
     TicketPaymentOptionsByGuestWeight { guest_weight: u64 },
     /// All payment options available
     #[returns(Vec<TicketPaymentOption>)]
     AllTicketPaymentOptions {},
+    /// Ticket revenue claimable by the curator now, still locked in escrow, and already claimed
+    #[returns(EscrowStatusResponse)]
+    EscrowStatus {},
+    /// Current phase of the event, and when it moves to the next
+    #[returns(StatusResponse)]
+    Status {},
+    /// Revenue accrued & withdrawn by a revenue split payee
+    #[returns(PayeeRevenueRes)]
+    PayeeRevenue { payee: String },
+    /// Revenue accrued & withdrawn by every revenue split payee
+    #[returns(Vec<PayeeRevenueRes>)]
+    AllPayeeRevenue {},
+    /// Receipt of what was paid for a specific ticket
+    #[returns(TicketReceipt)]
+    TicketReceipt { ticket_addr: String },
+    /// Receipts of all tickets paid for by a wallet
+    #[returns(Vec<TicketReceiptRes>)]
+    TicketReceiptsByPurchaser {
+        purchaser: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Receipts of all tickets sold for a specific guest type
+    #[returns(Vec<TicketReceiptRes>)]
+    TicketReceiptsByGuestType {
+        guest_weight: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Resale listing of a specific ticket
+    #[returns(ResaleListing)]
+    ResaleListing { ticket_addr: String },
+    /// All tickets listed for resale
+    #[returns(Vec<ResaleListingRes>)]
+    ResaleListings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Homie checkin secret commitment of a ticket, if set
+    #[returns(Option<HomieCheckinSecret>)]
+    HomieCheckinSecret { ticket_addr: String },
+    /// How many more tickets of each guest type a wallet can purchase
+    #[returns(Vec<TicketAllowanceRes>)]
+    TicketAllowance { purchaser: String },
+    /// Purchases waiting for a ticket of a guest type, in the order they are promoted
+    #[returns(Vec<WaitlistEntryRes>)]
+    Waitlist {
+        guest_weight: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Waitlist entry of a specific ticket address
+    #[returns(WaitlistEntryRes)]
+    WaitlistEntry { ticket_addr: String },
+    /// Segments & gates an usher is restricted to, if any
+    #[returns(Option<UsherScope>)]
+    UsherScope { usher: String },
+    /// Current members of the event usher group, with their weights
+    #[returns(Vec<Member>)]
+    Ushers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// New ticket limits & prices of an existing guest type, unchanged if not set.
+#[cw_serde]
+pub struct GuestTypeUpdate {
+    pub guest_weight: u64,
+    /// cannot be less than the tickets already sold, including waitlisted purchases promoted
+    pub total_ticket_limit: Option<u32>,
+    pub max_ticket_limit: Option<u32>,
+    /// prices of tickets sold from now on
+    pub ticket_cost: Option<Vec<Coin>>,
+    pub cw20_ticket_cost: Option<Vec<Cw20Coin>>,
+}
+
+/// New times of an event segment that has not started yet.
+#[cw_serde]
+pub struct SegmentSchedule {
+    pub segment_id: u64,
+    pub start: Timestamp,
+    pub end: Timestamp,
 }
 
 #[cw_serde]
@@ -91,5 +359,82 @@ pub struct EventSegmentRes {
     pub segment: EventSegment,
 }
 
+#[cw_serde]
+pub struct OccupancyRes {
+    pub segment_id: u64,
+    /// guests checked in & not since checked out
+    pub occupancy: u32,
+    /// most guests allowed inside at once, uncapped if not set
+    pub capacity: Option<u32>,
+}
+
+#[cw_serde]
+pub struct TicketReceiptRes {
+    pub ticket_addr: String,
+    pub receipt: TicketReceipt,
+}
+
+#[cw_serde]
+pub struct ResaleListingRes {
+    pub ticket_addr: String,
+    pub listing: ResaleListing,
+}
+
+#[cw_serde]
+pub struct TicketAllowanceRes {
+    pub guest_weight: u64,
+    pub guest_type: String,
+    /// tickets & waitlisted purchases already paid for by the wallet
+    pub reserved: u32,
+    /// tickets the wallet can still purchase, unlimited if not set
+    pub remaining: Option<u32>,
+}
+
+#[cw_serde]
+pub struct WaitlistEntryRes {
+    pub position: u64,
+    /// number of entries ahead in the waitlist of this guest type
+    pub ahead: u32,
+    pub entry: WaitlistEntry,
+}
+
+#[cw_serde]
+pub struct PayeeRevenueRes {
+    pub payee: Addr,
+    /// shares of released ticket revenue, including what was withdrawn
+    pub accrued: Vec<Coin>,
+    pub withdrawn: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct StatusResponse {
+    pub phase: EventPhase,
+    /// phase the event moves to next, if any
+    pub next_phase: Option<EventPhase>,
+    /// when the event moves to the next phase, if driven by block time rather than the curator
+    pub next_transition: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct EscrowStatusResponse {
+    /// share of the curator released by ended event segments, not yet claimed.
+    /// Includes cancellation refunds left unclaimed once the refund window closed
+    pub claimable: Vec<Coin>,
+    /// waiting on event segments that have not ended yet
+    pub locked: Vec<Coin>,
+    /// already paid out to the curator
+    pub claimed: Vec<Coin>,
+    /// set aside for refunds of cancelled event segments, while ticket holders can still claim them
+    pub refundable: Vec<Coin>,
+    /// when the last refund window of cancelled event segments closes, if any event segment was cancelled
+    pub refund_deadline: Option<Timestamp>,
+}
+
+/// Queries of the cw-ave-factory used by events, which cannot depend on the factory itself.
+#[cw_serde]
+pub enum FactoryQueryMsg {
+    Ownership {},
+}
+
 #[cw_serde]
 pub struct MigrateMsg {}
@@ -1,25 +1,70 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Checksum, Coin, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Checksum, Coin, Timestamp, Uint128};
+use cw20::Cw20Coin;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use sha2::{Digest, Sha256};
 
 pub const CONFIG: Item<Config> = Item::new("c");
 /// Details about a specific guest type of an event (1 day, 2 day, vip, etc)
 pub const GUEST_DETAILS: Map<u64, GuestDetails> = Map::new("gd");
-/// A count of all tickets for a specific guest type.
-/// Does not take into account the ticket type, just the total number of tickets available to checkin with for a given ticket_address
-pub const RESERVED_TICKETS: Map<&u64, u128> = Map::new("rt");
+/// Tickets & waitlisted purchases a wallet has paid for, by (purchaser, guest_weight).
+/// Includes tickets reserved for homies, and is limited by the `max_ticket_limit` of the guest type.
+pub const RESERVED_TICKETS: Map<(&Addr, u64), u32> = Map::new("wrt");
 /// Total amount of tickets reseved for a given guest weight:
 /// ex:(event_segment,tickets_reserved)
 pub const TOTAL_RESERVED_BY_GUEST_TYPE: Map<u64, u32> = Map::new("trbg");
 
 pub const EVENT_STAGES: Map<u64, EventSegment> = Map::new("es");
 
-pub const ATTENDANCE_RECORD: Map<(&String, u64), bool> = Map::new("rt");
+/// Segments & gates ushers are restricted to checking guests in at, by usher address.
+/// Ushers without a scope can checkin any segment, at any gate.
+pub const USHER_SCOPES: Map<&Addr, UsherScope> = Map::new("us");
+/// Guests currently inside each event segment, checked in & not since checked out.
+pub const OCCUPANCY: Map<u64, u32> = Map::new("occ");
+/// Guests checked in to event segments before their checkin window opened, by segment id.
+/// They are counted in the occupancy of a segment once its checkin window opens.
+pub const EARLY_OCCUPANCY: Map<u64, u32> = Map::new("eocc");
+/// Checkin signature nonces already used, by (ticket_addr, nonce).
+pub const USED_CHECKIN_NONCES: Map<(&String, u64), bool> = Map::new("ucn");
 /// A list of tickets that a wallet has reserved for a different address than paid.
 pub const HOMIE_TICKETS: Map<&String, Vec<String>> = Map::new("ht");
 
 pub const LICENSE_ADDR: Item<Addr> = Item::new("laddr");
+/// cw20 token contracts accepted as ticket payment.
+/// Ticket payments in cw20 tokens use the token contract address as their denom.
+pub const CW20_PAYMENT_TOKENS: Map<&str, bool> = Map::new("cw20pt");
+/// Ticket revenue (net of the dev fee) held in escrow, by guest weight & payment denom.
+/// Released to the curator as the event segments a guest type has access to end.
+pub const ESCROWED_REVENUE: Map<(u64, &str), Uint128> = Map::new("er");
+/// Escrowed ticket revenue already paid out to the curator, by payment denom.
+pub const CLAIMED_REVENUE: Map<&str, Uint128> = Map::new("cr");
+/// Tickets issued of each guest type, by guest weight & payment denom.
+/// Revenue splits paid per ticket accrue for each of them.
+pub const TICKETS_SOLD: Map<(u64, &str), u32> = Map::new("ts");
+/// Ticket revenue already withdrawn by each revenue split payee, by (payee, denom).
+pub const WITHDRAWN_REVENUE_SHARES: Map<(&Addr, &str), Uint128> = Map::new("wrs");
+/// Cancelled event segments, with when the refund window of each closes. Guests cannot checkin to cancelled segments,
+/// and their share of escrowed ticket revenue is refunded instead of released to the curator.
+pub const CANCELLED_SEGMENTS: Map<u64, Timestamp> = Map::new("cseg");
+/// Ticket revenue set aside for refunds of cancelled segments, by segment id, guest weight & payment denom.
+/// What is left once the refund window of a segment closes is released to the curator.
+pub const REFUND_POOL: Map<(u64, u64, &str), Uint128> = Map::new("rp");
+/// Cancellation refunds already claimed for a ticket, by ticket address & segment id.
+pub const CANCELLATION_REFUNDS: Map<(&str, u64), Uint128> = Map::new("cnr");
+/// Hash commitments of secrets a homie can use to be checked in with a ticket, by ticket address.
+/// Removed once used, revoked, or the ticket changes hands.
+pub const HOMIE_CHECKIN_SECRETS: Map<&str, HomieCheckinSecret> = Map::new("hcs");
+/// Tickets listed for resale, by ticket address. Listed tickets cannot check in until sold or cancelled.
+pub const RESALE_LISTINGS: Map<&str, ResaleListing> = Map::new("rl");
+/// Purchases waiting for a ticket of a sold out guest type, by (guest_weight, position).
+/// Positions only increase, so each guest type is promoted from in first in, first out order.
+pub const WAITLIST: Map<(u64, u64), WaitlistEntry> = Map::new("wl");
+/// Position on the waitlist of a ticket address, as (guest_weight, position).
+pub const WAITLISTED_TICKETS: Map<&str, (u64, u64)> = Map::new("wlt");
+/// Number of purchases waiting on the waitlist of a guest type.
+pub const WAITLIST_COUNT: Map<u64, u32> = Map::new("wlc");
+/// Position given to the next purchase joining any waitlist.
+pub const NEXT_WAITLIST_POSITION: Item<u64> = Item::new("nwp");
 #[cw_serde]
 pub struct Config {
     pub curator: Addr,
@@ -27,6 +72,334 @@ pub struct Config {
     pub event_guest_contract: Addr,
     pub title: String,
     pub description: String,
+    #[serde(default)]
+    pub resale: ResaleSettings,
+    /// how the usher group was set up
+    #[serde(default)]
+    pub usher_group: GroupMode,
+    /// how the guest group was set up
+    #[serde(default)]
+    pub guest_group: GroupMode,
+    /// lowest usher weight of supervisors, there are no supervisors if not set
+    #[serde(default)]
+    pub supervisor_weight: Option<u64>,
+    /// proof-of-attendance collection minted to as guests check in, if any
+    #[serde(default)]
+    pub poap_collection: Option<Addr>,
+    /// lifecycle state set by the curator, the phase of published events follows their segment times
+    #[serde(default)]
+    pub lifecycle: LifecycleState,
+    /// contract that instantiated the event, normally the cw-ave-factory. Its owner can cancel the event
+    #[serde(default)]
+    pub factory: Option<Addr>,
+    /// shares of released ticket revenue paid to payees other than the curator, such as venues & performers
+    #[serde(default)]
+    pub revenue_splits: Vec<RevenueSplit>,
+    /// WebAuthn relying party id passkey checkins must be made for, passkey checkins are refused if not set
+    #[serde(default)]
+    pub webauthn_rp_id: Option<String>,
+}
+
+/// Share of released ticket revenue a payee can withdraw.
+#[cw_serde]
+pub struct RevenueSplit {
+    pub payee: Addr,
+    pub share: RevenueShare,
+}
+
+#[cw_serde]
+pub enum RevenueShare {
+    /// basis points of released revenue, after shares paid per ticket
+    Bps { bps: u64 },
+    /// fixed amount for each ticket of a guest type, in the denoms it was paid in.
+    /// Accrues as the escrow of the guest type is released, and never more than was released
+    PerTicket {
+        guest_weight: u64,
+        amount: Vec<Coin>,
+    },
+}
+
+/// Lifecycle state of an event, changed by its curator.
+#[cw_serde]
+#[derive(Default)]
+pub enum LifecycleState {
+    /// being prepared, tickets are not on sale until the curator opens sales
+    Draft,
+    /// on sale, live or ended, following the start & end of its event segments
+    #[default]
+    Published,
+    /// cancelled by the curator
+    Cancelled,
+    /// ended & paid out to the curator
+    Settled,
+}
+
+/// Current phase of an event, derived from its lifecycle state & block time.
+#[cw_serde]
+pub enum EventPhase {
+    Draft,
+    /// tickets on sale, no event segment has started yet
+    OnSale,
+    /// the first event segment started, the last has not ended
+    Live,
+    /// the last event segment ended, waiting to be settled
+    Ended,
+    Cancelled,
+    Settled,
+}
+
+impl std::fmt::Display for EventPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            EventPhase::Draft => "draft",
+            EventPhase::OnSale => "on sale",
+            EventPhase::Live => "live",
+            EventPhase::Ended => "ended",
+            EventPhase::Cancelled => "cancelled",
+            EventPhase::Settled => "settled",
+        })
+    }
+}
+
+/// Proof-of-attendance collection of an event, instantiated next to its usher & guest groups.
+#[cw_serde]
+pub struct PoapSettings {
+    /// code-id of the cw721-poap contract
+    pub cw721: u64,
+    /// whether tokens can be transferred, or are soulbound to the ticket checked in
+    pub transferable: bool,
+}
+
+/// Segments & gates an usher is restricted to checking guests in at.
+/// Supervisors are never restricted.
+#[cw_serde]
+pub struct UsherScope {
+    /// event segments the usher can checkin, any segment if empty
+    pub segment_ids: Vec<u64>,
+    /// gates the usher can checkin at, any gate if empty
+    pub gates: Vec<String>,
+}
+
+/// How an usher or guest group of an event was set up.
+#[cw_serde]
+#[derive(Default)]
+pub enum GroupMode {
+    /// cw420 group instantiated & administered by the event
+    #[default]
+    Instantiated,
+    /// existing cw4 group, whose members the event can only update if it is the group admin
+    Existing { writable: bool },
+}
+
+impl GroupMode {
+    /// whether the event can update the members of the group
+    pub fn writable(&self) -> bool {
+        match self {
+            GroupMode::Instantiated => true,
+            GroupMode::Existing { writable } => *writable,
+        }
+    }
+}
+
+/// Commitment to a secret that checks in a ticket without its key signing.
+#[cw_serde]
+pub struct HomieCheckinSecret {
+    /// sha256 hash of the secret
+    pub secret_hash: Binary,
+    /// holder of the ticket that set the secret
+    pub set_by: Addr,
+}
+
+/// Terms of reselling tickets of an event.
+#[cw_serde]
+#[derive(Default)]
+pub struct ResaleSettings {
+    /// share of each resale paid to the curator, in basis points
+    pub royalty_bps: u64,
+    /// highest resale price, in basis points of the face value of the ticket in the same denom.
+    /// Resale prices are uncapped if not set.
+    pub max_price_bps: Option<u64>,
+}
+
+/// A ticket listed for resale.
+#[cw_serde]
+pub struct ResaleListing {
+    /// wallet paid when the listing is filled
+    pub seller: Addr,
+    pub guest_weight: u64,
+    /// price of the ticket, in any denom accepted for its guest type.
+    /// capped relative to what was paid for the ticket
+    pub price: Coin,
+    pub listed_at: Timestamp,
+}
+
+/// Attendance of a ticket for an event segment.
+#[cw_serde]
+pub struct AttendanceRecord {
+    pub ticket_addr: String,
+    pub segment_id: u64,
+    pub checked_in: bool,
+    /// who checked the ticket in last, when & where. Not known for checkins made before they were recorded.
+    /// every checkin is kept in the checkin log
+    pub checkin: Option<CheckInRecord>,
+    /// whether the guest checked out since their last checkin
+    #[serde(default)]
+    pub checked_out: bool,
+    /// times the guest re-entered after checking out
+    #[serde(default)]
+    pub reentries: u32,
+    /// who checked the ticket out last, when & where
+    #[serde(default)]
+    pub checkout: Option<CheckOutRecord>,
+}
+
+impl AttendanceRecord {
+    /// whether the guest is currently inside the event segment
+    pub fn inside(&self) -> bool {
+        self.checked_in && !self.checked_out
+    }
+}
+
+/// Checkin of a ticket to an event segment.
+#[cw_serde]
+pub struct CheckInRecord {
+    /// block time of the checkin
+    pub time: Timestamp,
+    /// block height of the checkin
+    pub height: u64,
+    /// usher that checked the ticket in
+    pub usher: Addr,
+    /// gate the ticket was checked in at, if named
+    pub gate: Option<String>,
+}
+
+/// A checkin of a ticket to an event segment, as kept in the checkin log.
+#[cw_serde]
+pub struct CheckInLogEntry {
+    pub ticket_addr: String,
+    pub segment_id: u64,
+    /// checkins of the ticket to the segment before this one, the first checkin is 0
+    pub seq: u32,
+    pub checkin: CheckInRecord,
+}
+
+/// When, where & by whom a ticket was checked out of an event segment.
+#[cw_serde]
+pub struct CheckOutRecord {
+    /// block time of the checkout
+    pub time: Timestamp,
+    /// block height of the checkout
+    pub height: u64,
+    /// usher that checked the ticket out, or the ticket itself
+    pub by: Addr,
+    /// gate the ticket was checked out at, if named
+    pub gate: Option<String>,
+}
+
+pub struct AttendanceIndexes<'a> {
+    pub segment: MultiIndex<'a, u64, AttendanceRecord, (String, u64)>,
+}
+
+impl IndexList<AttendanceRecord> for AttendanceIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AttendanceRecord>> + '_> {
+        let v: Vec<&dyn Index<AttendanceRecord>> = vec![&self.segment];
+        Box::new(v.into_iter())
+    }
+}
+
+pub struct CheckInLogIndexes<'a> {
+    /// usher that made the checkin
+    pub usher: MultiIndex<'a, String, CheckInLogEntry, (String, u64, u32)>,
+}
+
+impl IndexList<CheckInLogEntry> for CheckInLogIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CheckInLogEntry>> + '_> {
+        let v: Vec<&dyn Index<CheckInLogEntry>> = vec![&self.usher];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Record of what was paid for a ticket.
+#[cw_serde]
+pub struct TicketReceipt {
+    /// wallet that paid for the ticket, and is refunded if the ticket is refunded
+    pub purchaser: Addr,
+    /// guest type of the ticket
+    pub guest_weight: u64,
+    /// denom the ticket was paid with
+    pub denom: String,
+    /// amount paid for the ticket
+    pub price: Uint128,
+    /// non-refundable portion of `price` sent as the dev fee
+    pub dev_fee: Uint128,
+    /// block time of the purchase
+    pub purchased_at: Timestamp,
+}
+
+pub struct ReceiptIndexes<'a> {
+    pub purchaser: MultiIndex<'a, String, TicketReceipt, String>,
+    pub guest_weight: MultiIndex<'a, u64, TicketReceipt, String>,
+}
+
+impl IndexList<TicketReceipt> for ReceiptIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TicketReceipt>> + '_> {
+        let v: Vec<&dyn Index<TicketReceipt>> = vec![&self.purchaser, &self.guest_weight];
+        Box::new(v.into_iter())
+    }
+}
+
+/// A purchase waiting for a ticket of a sold out guest type.
+/// The full price is held by the event until it is promoted to a ticket or withdrawn,
+/// the dev fee in its receipt is only taken once promoted.
+#[cw_serde]
+pub struct WaitlistEntry {
+    pub ticket_addr: String,
+    pub receipt: TicketReceipt,
+}
+
+/// Attendance of tickets, by (ticket_addr, event_segment_id).
+/// Records exist for each segment the guest type of a ticket has access to, created when purchased.
+pub fn attendance_records<'a>(
+) -> IndexedMap<(&'a String, u64), AttendanceRecord, AttendanceIndexes<'a>> {
+    let indexes = AttendanceIndexes {
+        segment: MultiIndex::new(
+            |_pk: &[u8], r: &AttendanceRecord| r.segment_id,
+            "ar",
+            "ar__segment",
+        ),
+    };
+    IndexedMap::new("ar", indexes)
+}
+
+/// Every checkin of tickets to event segments, by (ticket_addr, event_segment_id, seq).
+/// Entries are only added, re-entries do not replace earlier checkins.
+pub fn checkin_log<'a>(
+) -> IndexedMap<(&'a String, u64, u32), CheckInLogEntry, CheckInLogIndexes<'a>> {
+    let indexes = CheckInLogIndexes {
+        usher: MultiIndex::new(
+            |_pk: &[u8], e: &CheckInLogEntry| e.checkin.usher.to_string(),
+            "cl",
+            "cl__usher",
+        ),
+    };
+    IndexedMap::new("cl", indexes)
+}
+
+/// Receipts of every purchased ticket, keyed by ticket address.
+pub fn ticket_receipts<'a>() -> IndexedMap<&'a str, TicketReceipt, ReceiptIndexes<'a>> {
+    let indexes = ReceiptIndexes {
+        purchaser: MultiIndex::new(
+            |_pk: &[u8], r: &TicketReceipt| r.purchaser.to_string(),
+            "ticket_receipts",
+            "ticket_receipts__purchaser",
+        ),
+        guest_weight: MultiIndex::new(
+            |_pk: &[u8], r: &TicketReceipt| r.guest_weight,
+            "ticket_receipts",
+            "ticket_receipts__guest_weight",
+        ),
+    };
+    IndexedMap::new("ticket_receipts", indexes)
 }
 
 #[cw_serde]
@@ -52,10 +425,16 @@ pub struct RegisteringGuest {
 
 #[cw_serde]
 pub struct CheckInSignatureData {
+    /// event contract the guest is checking into
     pub event_contract_addr: String,
     pub event_segment_ids: Vec<u64>,
     pub homies_tickets: Vec<String>,
+    /// usher allowed to submit this checkin
     pub usher_wallet_addr: String,
+    /// unique value per signature, each nonce can be used once per ticket address
+    pub nonce: u64,
+    /// time after which this signature can no longer be used to checkin
+    pub expires_at: Timestamp,
 }
 
 #[cw_serde]
@@ -68,6 +447,40 @@ pub struct CheckInDetails {
     pub ticket_addr: String,
     /// cosmos_sdk_proto::Any of the pubkey that generated the signature
     pub pubkey: Binary,
+    /// scheme used to generate the signature, defaults to ADR-036 with secp256k1
+    pub scheme: Option<CheckInSignatureScheme>,
+    /// gate the guest is checking in at, set by the usher
+    #[serde(default)]
+    pub gate: Option<String>,
+    /// checkin outside of the checkin windows of the event segments. Supervisors only.
+    #[serde(default)]
+    pub supervisor_override: bool,
+}
+
+/// Signature schemes a guest can sign their checkin with.
+#[cw_serde]
+pub enum CheckInSignatureScheme {
+    /// ADR-036 sign doc signed with a secp256k1 key
+    Adr036Secp256k1 {},
+    /// ADR-036 sign doc signed with an ed25519 key
+    Adr036Ed25519 {},
+    /// WebAuthn (passkey) assertion signed with a secp256r1 key.
+    /// The challenge is the base64url encoded sha256 hash of the ADR-036 sign doc.
+    WebAuthnSecp256r1 {
+        /// authenticator data of the assertion
+        authenticator_data: Binary,
+        /// client data json of the assertion, containing the challenge
+        client_data_json: String,
+    },
+}
+
+/// How a batch of checkins handles an invalid checkin.
+#[cw_serde]
+pub enum BatchCheckInMode {
+    /// the batch fails on the first invalid checkin
+    FailFast,
+    /// invalid checkins are skipped & reported in the `checkin_skipped` attribute
+    SkipInvalid,
 }
 
 /// Defines timelengths of a specific stage of an event.
@@ -77,12 +490,38 @@ pub struct EventSegment {
     pub stage_description: String,
     pub start: Timestamp,
     pub end: Timestamp,
+    /// share of escrowed ticket revenue released once this segment ends. Defaults to 1.
+    pub escrow_weight: Option<u64>,
+    /// seconds before `start` that guests can begin checking in
+    pub early_checkin_grace: Option<u64>,
+    /// seconds after `end` that guests can still check in
+    pub late_checkin_grace: Option<u64>,
+    /// most guests that can be inside this segment at once, uncapped if not set
+    pub capacity: Option<u32>,
+}
+
+impl EventSegment {
+    /// (opens, closes) times guests can check in to this segment, including grace periods.
+    pub fn checkin_window(&self) -> (Timestamp, Timestamp) {
+        let opens = self.start.nanos().saturating_sub(
+            self.early_checkin_grace
+                .unwrap_or_default()
+                .saturating_mul(1_000_000_000),
+        );
+        let closes = self.end.nanos().saturating_add(
+            self.late_checkin_grace
+                .unwrap_or_default()
+                .saturating_mul(1_000_000_000),
+        );
+        (Timestamp::from_nanos(opens), Timestamp::from_nanos(closes))
+    }
 }
 
 #[cw_serde]
 pub struct TicketPaymentOption {
     pub guest_type: String,
     pub payment_options: Vec<Coin>,
+    pub cw20_payment_options: Vec<Cw20Coin>,
 }
 
 #[cw_serde]
@@ -91,16 +530,61 @@ pub struct GuestDetails {
     pub guest_type: String,
     /// weight used in cw420 to distinguish guest types
     pub guest_weight: u64,
-    /// limit to number of tickets a guest can purchase
+    /// limit to number of tickets a single wallet can purchase, including those for homies. 0 is unlimited
     pub max_ticket_limit: u32,
     /// the total amount of tickets available for this guest type
     pub total_ticket_limit: u32,
     /// limit to number of this type of guests
     // pub max_guest_limit: u32,
-    // pub overbooking_limit: u32,
+    /// number of purchases that can join the waitlist once sold out, with their payment escrowed.
+    /// Set to 0 to disable, rejecting purchases once sold out.
+    #[serde(default)]
+    pub overbooking_limit: u32,
     /// array of coins accepted for ticket
     pub ticket_cost: Vec<Coin>,
+    /// array of cw20 tokens accepted for ticket, purchased by sending them with `ReceiveMsg::PurchaseTickets`
+    #[serde(default)]
+    pub cw20_ticket_cost: Vec<Cw20Coin>,
     pub event_segment_access: EventSegmentAccessType,
+    /// whether guests can enter a segment again after checking out
+    #[serde(default)]
+    pub reentry: ReentryRule,
+}
+
+/// Re-entries allowed to guests that checked out of an event segment.
+#[cw_serde]
+#[derive(Default)]
+pub enum ReentryRule {
+    /// guests cannot re-enter once checked out
+    #[default]
+    Never,
+    /// guests can re-enter any number of times
+    Unlimited,
+    /// guests can re-enter up to `max` times
+    Limited { max: u32 },
+}
+
+impl ReentryRule {
+    /// whether a guest that already re-entered `reentries` times can re-enter again
+    pub fn allows(&self, reentries: u32) -> bool {
+        match self {
+            ReentryRule::Never => false,
+            ReentryRule::Unlimited => true,
+            ReentryRule::Limited { max } => reentries < *max,
+        }
+    }
+}
+
+impl GuestDetails {
+    /// every accepted ticket price, with cw20 prices denominated by their token contract address.
+    pub fn payment_options(&self) -> Vec<Coin> {
+        let mut options = self.ticket_cost.clone();
+        options.extend(self.cw20_ticket_cost.iter().map(|c| Coin {
+            denom: c.address.clone(),
+            amount: c.amount,
+        }));
+        options
+    }
 }
 
 pub fn generate_instantiate_salt2(checksum: &Checksum, namespace: &[u8]) -> Binary {
@@ -128,6 +612,20 @@ pub enum EventSegmentAccessType {
     },
 }
 
+impl EventSegmentAccessType {
+    /// ids of every event segment this access type grants entry to, without duplicates.
+    pub fn segment_ids(&self) -> Vec<u64> {
+        let mut ids = match self {
+            EventSegmentAccessType::SingleSegment { id } => vec![*id],
+            EventSegmentAccessType::AnyOfSpecificSegments { ids } => ids.clone(),
+            EventSegmentAccessType::AllOfSpecificSegments { ids } => ids.clone(),
+        };
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
 pub fn preamble_msg_arb_036(signer: &str, data: &str) -> String {
     format!(
         "{{\"account_number\":\"0\",\"chain_id\":\"\",\"fee\":{{\"amount\":[],\"gas\":\"0\"}},\"memo\":\"\",\"msgs\":[{{\"type\":\"sign/MsgSignData\",\"value\":{{\"data\":\"{}\",\"signer\":\"{}\"}}}}],\"sequence\":\"0\"}}", 
@@ -0,0 +1,348 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+
+use crate::error::ContractError;
+use crate::msg::{
+    AllNftInfoResponse, Approval, ContractInfoResponse, Cw721ReceiveMsg, ExecuteMsg,
+    InstantiateMsg, MinterResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse,
+    PoapMetadata, QueryMsg, TokensResponse,
+};
+use crate::state::{tokens, CollectionConfig, TokenInfo, CONFIG, TOKEN_COUNT};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-poap";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONFIG.save(
+        deps.storage,
+        &CollectionConfig {
+            name: msg.name,
+            symbol: msg.symbol,
+            minter: deps.api.addr_validate(&msg.minter)?,
+            transferable: msg.transferable,
+        },
+    )?;
+    TOKEN_COUNT.save(deps.storage, &0)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Mint {
+            token_id,
+            owner,
+            token_uri,
+            extension,
+        } => execute_mint(deps, info, token_id, owner, token_uri, extension),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            transfer(deps, &env, &info, &recipient, &token_id)?;
+            Ok(Response::new()
+                .add_attribute("action", "transfer_nft")
+                .add_attribute("sender", info.sender)
+                .add_attribute("recipient", recipient)
+                .add_attribute("token_id", token_id))
+        }
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            transfer(deps, &env, &info, &contract, &token_id)?;
+            let receive = Cw721ReceiveMsg {
+                sender: info.sender.to_string(),
+                token_id: token_id.clone(),
+                msg,
+            }
+            .into_cosmos_msg(contract.to_string())?;
+            Ok(Response::new()
+                .add_message(receive)
+                .add_attribute("action", "send_nft")
+                .add_attribute("sender", info.sender)
+                .add_attribute("recipient", contract)
+                .add_attribute("token_id", token_id))
+        }
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => execute_approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => execute_revoke(deps, info, spender, token_id),
+        ExecuteMsg::Burn { token_id } => execute_burn(deps, info, token_id),
+    }
+}
+
+pub fn execute_mint(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: PoapMetadata,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if info.sender != cfg.minter {
+        return Err(ContractError::Unauthorized {});
+    }
+    let token = TokenInfo {
+        owner: deps.api.addr_validate(&owner)?,
+        approvals: vec![],
+        token_uri,
+        extension,
+    };
+    tokens().update(deps.storage, &token_id, |old| match old {
+        Some(_) => Err(ContractError::Claimed {}),
+        None => Ok(token),
+    })?;
+    TOKEN_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("minter", info.sender)
+        .add_attribute("owner", owner)
+        .add_attribute("token_id", token_id))
+}
+
+/// moves a token to the recipient, clearing its approvals.
+/// the sender must own the token or hold an unexpired approval for it, and tokens must be transferable.
+fn transfer(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    if !CONFIG.load(deps.storage)?.transferable {
+        return Err(ContractError::Soulbound {});
+    }
+    let mut token = tokens().load(deps.storage, token_id)?;
+    let approved = token
+        .approvals
+        .iter()
+        .any(|a| a.spender == info.sender && !a.expires.is_expired(&env.block));
+    if token.owner != info.sender && !approved {
+        return Err(ContractError::Unauthorized {});
+    }
+    token.owner = recipient.clone();
+    token.approvals = vec![];
+    tokens().save(deps.storage, token_id, &token)?;
+    Ok(())
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    if !CONFIG.load(deps.storage)?.transferable {
+        return Err(ContractError::Soulbound {});
+    }
+    let mut token = tokens().load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    token.approvals.retain(|a| a.spender != spender_addr);
+    token.approvals.push(Approval {
+        spender: spender_addr,
+        expires,
+    });
+    tokens().save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("sender", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let mut token = tokens().load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let len = token.approvals.len();
+    token.approvals.retain(|a| a.spender != spender_addr);
+    if token.approvals.len() == len {
+        return Err(ContractError::ApprovalNotFound { spender });
+    }
+    tokens().save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke")
+        .add_attribute("sender", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let token = tokens().load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    tokens().remove(deps.storage, &token_id)?;
+    TOKEN_COUNT.update(deps.storage, |count| -> StdResult<_> {
+        Ok(count.saturating_sub(1))
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("sender", info.sender)
+        .add_attribute("token_id", token_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => to_json_binary(&query_owner_of(
+            deps,
+            &env.block,
+            &token_id,
+            include_expired.unwrap_or_default(),
+        )?),
+        QueryMsg::NftInfo { token_id } => to_json_binary(&query_nft_info(deps, &token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_json_binary(&AllNftInfoResponse {
+            access: query_owner_of(
+                deps,
+                &env.block,
+                &token_id,
+                include_expired.unwrap_or_default(),
+            )?,
+            info: query_nft_info(deps, &token_id)?,
+        }),
+        QueryMsg::NumTokens {} => to_json_binary(&NumTokensResponse {
+            count: TOKEN_COUNT.load(deps.storage)?,
+        }),
+        QueryMsg::ContractInfo {} => {
+            let cfg = CONFIG.load(deps.storage)?;
+            to_json_binary(&ContractInfoResponse {
+                name: cfg.name,
+                symbol: cfg.symbol,
+            })
+        }
+        QueryMsg::Minter {} => to_json_binary(&MinterResponse {
+            minter: Some(CONFIG.load(deps.storage)?.minter.to_string()),
+        }),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_json_binary(&query_all_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::Transferable {} => to_json_binary(&CONFIG.load(deps.storage)?.transferable),
+    }
+}
+
+pub fn query_owner_of(
+    deps: Deps,
+    block: &BlockInfo,
+    token_id: &str,
+    include_expired: bool,
+) -> StdResult<OwnerOfResponse> {
+    let token = tokens().load(deps.storage, token_id)?;
+    Ok(OwnerOfResponse {
+        owner: token.owner.to_string(),
+        approvals: token
+            .approvals
+            .into_iter()
+            .filter(|a| include_expired || !a.expires.is_expired(block))
+            .collect(),
+    })
+}
+
+pub fn query_nft_info(deps: Deps, token_id: &str) -> StdResult<NftInfoResponse> {
+    let token = tokens().load(deps.storage, token_id)?;
+    Ok(NftInfoResponse {
+        token_uri: token.token_uri,
+        extension: token.extension,
+    })
+}
+
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let owner = deps.api.addr_validate(&owner)?;
+    let start = start_after.map(Bound::exclusive);
+
+    let tokens = tokens()
+        .idx
+        .owner
+        .prefix(owner)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let tokens = tokens()
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokensResponse { tokens })
+}
@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("token_id already claimed")]
+    Claimed {},
+
+    #[error("tokens of this collection are soulbound, and cannot be transferred or approved")]
+    Soulbound {},
+
+    #[error("Approval not found for: {spender}")]
+    ApprovalNotFound { spender: String },
+
+    #[error("Cannot set approval that is already expired")]
+    Expired {},
+}
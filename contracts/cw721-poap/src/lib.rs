@@ -0,0 +1,18 @@
+/*!
+A minimal implementation of the [cw721 spec](https://github.com/public-awesome/cw-nfts/blob/main/packages/cw721/README.md),
+for proof-of-attendance tokens (POAPs) of an event.
+
+Tokens can only be minted by the minter, the event contract instantiating the collection, which mints one
+for each event segment a guest checks in to. The event curator chooses whether tokens can be transferred,
+or are soulbound to the ticket they were minted to. Soulbound tokens can still be burnt by their owner.
+*/
+
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,168 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, StdResult, WasmMsg};
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// name of the collection, usually the event title
+    pub name: String,
+    pub symbol: String,
+    /// the only account that can mint tokens
+    pub minter: String,
+    /// whether tokens can be transferred & approved. Tokens are soulbound if false
+    pub transferable: bool,
+}
+
+/// Metadata of a proof-of-attendance token.
+#[cw_serde]
+pub struct PoapMetadata {
+    /// title of the event attended
+    pub event: String,
+    /// description of the event segment attended
+    pub segment: String,
+    pub segment_id: u64,
+    /// guest type of the ticket checked in
+    pub guest_type: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Mint a new token. Callable by the minter
+    Mint {
+        token_id: String,
+        /// owner of the newly minted token
+        owner: String,
+        token_uri: Option<String>,
+        extension: PoapMetadata,
+    },
+    /// Transfer a token to another account, without triggering any action. Fails if tokens are soulbound
+    TransferNft { recipient: String, token_id: String },
+    /// Send a token to a contract, triggering its `ReceiveNft` hook. Fails if tokens are soulbound
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Allow another account to transfer a token. Fails if tokens are soulbound
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Remove a previously granted approval
+    Revoke { spender: String, token_id: String },
+    /// Burn a token. Callable by its owner, even if soulbound
+    Burn { token_id: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Owner of a token, with its approvals
+    #[returns(OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        /// include expired approvals
+        include_expired: Option<bool>,
+    },
+    #[returns(NftInfoResponse)]
+    NftInfo { token_id: String },
+    #[returns(AllNftInfoResponse)]
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Total number of tokens minted & not burnt
+    #[returns(NumTokensResponse)]
+    NumTokens {},
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
+    #[returns(MinterResponse)]
+    Minter {},
+    /// Tokens owned by an account
+    #[returns(TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Whether tokens can be transferred, or are soulbound
+    #[returns(bool)]
+    Transferable {},
+}
+
+#[cw_serde]
+pub struct Approval {
+    /// account that can transfer the token
+    pub spender: Addr,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    pub owner: String,
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: PoapMetadata,
+}
+
+#[cw_serde]
+pub struct AllNftInfoResponse {
+    pub access: OwnerOfResponse,
+    pub info: NftInfoResponse,
+}
+
+#[cw_serde]
+pub struct NumTokensResponse {
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: Option<String>,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+/// Message sent to the receiving contract of `SendNft`.
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+impl Cw721ReceiveMsg {
+    /// wraps the message as `ReceiveNft`, to be executed on the receiving contract
+    pub fn into_cosmos_msg(self, contract_addr: String) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_json_binary(&ReceiverExecuteMsg::ReceiveNft(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
@@ -0,0 +1,50 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+
+use crate::msg::{Approval, PoapMetadata};
+
+pub const CONFIG: Item<CollectionConfig> = Item::new("config");
+/// Number of tokens minted & not burnt.
+pub const TOKEN_COUNT: Item<u64> = Item::new("num_tokens");
+
+#[cw_serde]
+pub struct CollectionConfig {
+    pub name: String,
+    pub symbol: String,
+    pub minter: Addr,
+    /// whether tokens can be transferred & approved
+    pub transferable: bool,
+}
+
+#[cw_serde]
+pub struct TokenInfo {
+    pub owner: Addr,
+    /// accounts that can transfer this token, until they expire
+    pub approvals: Vec<Approval>,
+    pub token_uri: Option<String>,
+    pub extension: PoapMetadata,
+}
+
+pub struct TokenIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, TokenInfo, String>,
+}
+
+impl IndexList<TokenInfo> for TokenIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo>> + '_> {
+        let v: Vec<&dyn Index<TokenInfo>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Tokens by token id, indexed by owner.
+pub fn tokens<'a>() -> IndexedMap<&'a str, TokenInfo, TokenIndexes<'a>> {
+    let indexes = TokenIndexes {
+        owner: MultiIndex::new(
+            |_pk: &[u8], t: &TokenInfo| t.owner.clone(),
+            "tokens",
+            "tokens__owner",
+        ),
+    };
+    IndexedMap::new("tokens", indexes)
+}
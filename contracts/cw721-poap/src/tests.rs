@@ -0,0 +1,201 @@
+use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+use cosmwasm_std::{from_json, Addr, DepsMut};
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, NftInfoResponse, NumTokensResponse, OwnerOfResponse, PoapMetadata,
+    QueryMsg, TokensResponse,
+};
+use crate::ContractError;
+
+fn addr(name: &str) -> Addr {
+    MockApi::default().addr_make(name)
+}
+
+fn set_up(deps: DepsMut, transferable: bool) {
+    let msg = InstantiateMsg {
+        name: "Test Event".to_string(),
+        symbol: "POAP".to_string(),
+        minter: addr("event").to_string(),
+        transferable,
+    };
+    let info = message_info(&addr("creator"), &[]);
+    instantiate(deps, mock_env(), info, msg).unwrap();
+}
+
+fn mint_msg(token_id: &str, owner: &Addr) -> ExecuteMsg {
+    ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: PoapMetadata {
+            event: "Test Event".to_string(),
+            segment: "Main Event".to_string(),
+            segment_id: 0,
+            guest_type: "VIP".to_string(),
+        },
+    }
+}
+
+#[test]
+fn only_minter_can_mint() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut(), true);
+    let guest = addr("guest");
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&guest, &[]),
+        mint_msg("0", &guest),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let minter = message_info(&addr("event"), &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        minter.clone(),
+        mint_msg("0", &guest),
+    )
+    .unwrap();
+    // token ids are unique
+    let err = execute(deps.as_mut(), mock_env(), minter, mint_msg("0", &guest)).unwrap_err();
+    assert_eq!(err, ContractError::Claimed {});
+
+    let info: NftInfoResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::NftInfo {
+                token_id: "0".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(info.extension.guest_type, "VIP");
+    let count: NumTokensResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::NumTokens {}).unwrap()).unwrap();
+    assert_eq!(count.count, 1);
+}
+
+#[test]
+fn soulbound_tokens_cannot_move() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut(), false);
+    let guest = addr("guest");
+    let minter = message_info(&addr("event"), &[]);
+    execute(deps.as_mut(), mock_env(), minter, mint_msg("0", &guest)).unwrap();
+
+    let owner = message_info(&guest, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner.clone(),
+        ExecuteMsg::TransferNft {
+            recipient: addr("friend").to_string(),
+            token_id: "0".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Soulbound {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner.clone(),
+        ExecuteMsg::Approve {
+            spender: addr("friend").to_string(),
+            token_id: "0".to_string(),
+            expires: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Soulbound {});
+
+    // soulbound tokens can still be burnt by their owner
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner,
+        ExecuteMsg::Burn {
+            token_id: "0".to_string(),
+        },
+    )
+    .unwrap();
+    let count: NumTokensResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::NumTokens {}).unwrap()).unwrap();
+    assert_eq!(count.count, 0);
+}
+
+#[test]
+fn transferable_tokens_move_with_approvals() {
+    let mut deps = mock_dependencies();
+    set_up(deps.as_mut(), true);
+    let [guest, friend, stranger] = [addr("guest"), addr("friend"), addr("stranger")];
+    let minter = message_info(&addr("event"), &[]);
+    execute(deps.as_mut(), mock_env(), minter, mint_msg("0", &guest)).unwrap();
+
+    let transfer = ExecuteMsg::TransferNft {
+        recipient: friend.to_string(),
+        token_id: "0".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&stranger, &[]),
+        transfer.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&guest, &[]),
+        ExecuteMsg::Approve {
+            spender: stranger.to_string(),
+            token_id: "0".to_string(),
+            expires: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&stranger, &[]),
+        transfer,
+    )
+    .unwrap();
+
+    // approvals are cleared once transferred
+    let owner: OwnerOfResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::OwnerOf {
+                token_id: "0".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(owner.owner, friend.to_string());
+    assert!(owner.approvals.is_empty());
+    let tokens: TokensResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Tokens {
+                owner: friend.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tokens.tokens, vec!["0".to_string()]);
+}
@@ -0,0 +1,25 @@
+use cw_orch::{interface, prelude::*};
+
+use cw20_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+pub const CONTRACT_ID: &str = "cw20_base";
+
+#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty, id = CONTRACT_ID)]
+pub struct Cw20Base;
+
+impl<Chain> Uploadable for Cw20Base<Chain> {
+    /// Return the path to the wasm file corresponding to the contract
+    fn wasm(_chain: &ChainInfoOwned) -> WasmPath {
+        artifacts_dir_from_workspace!()
+            .find_wasm_path("cw20_base")
+            .unwrap()
+    }
+    /// Returns a CosmWasm contract wrapper
+    fn wrapper() -> Box<dyn MockContract<Empty>> {
+        Box::new(ContractWrapper::new_with_empty(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ))
+    }
+}
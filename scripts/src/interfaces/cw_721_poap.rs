@@ -0,0 +1,25 @@
+use cw_orch::{interface, prelude::*};
+
+use cw721_poap::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+pub const CONTRACT_ID: &str = "cw721_poap";
+
+#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty, id = CONTRACT_ID)]
+pub struct Cw721Poap;
+
+impl<Chain> Uploadable for Cw721Poap<Chain> {
+    /// Return the path to the wasm file corresponding to the contract
+    fn wasm(_chain: &ChainInfoOwned) -> WasmPath {
+        artifacts_dir_from_workspace!()
+            .find_wasm_path("cw721_poap")
+            .unwrap()
+    }
+    /// Returns a CosmWasm contract wrapper
+    fn wrapper() -> Box<dyn MockContract<Empty>> {
+        Box::new(ContractWrapper::new_with_empty(
+            cw721_poap::contract::execute,
+            cw721_poap::contract::instantiate,
+            cw721_poap::contract::query,
+        ))
+    }
+}
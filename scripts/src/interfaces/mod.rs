@@ -1,9 +1,13 @@
+pub mod cw_20;
 pub mod cw_420;
+pub mod cw_721_poap;
 pub mod cw_ave;
 pub mod cw_ave_factory;
 pub mod suite;
 
+pub use cw_20::Cw20Base;
 pub use cw_420::Cw420;
+pub use cw_721_poap::Cw721Poap;
 pub use cw_ave::CwAve;
 pub use cw_ave_factory::CwAveFactory;
 pub use suite::CwAveSuite;
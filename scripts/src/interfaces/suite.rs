@@ -3,13 +3,14 @@ use cw_orch::{
     prelude::{ContractInstance, CwEnv, CwOrchUpload, Deploy},
 };
 
-use crate::interfaces::{cw_420::Cw420, CwAve, CwAveFactory};
+use crate::interfaces::{cw_420::Cw420, Cw721Poap, CwAve, CwAveFactory};
 
 #[derive(Clone)]
 pub struct CwAveSuite<Chain: CwEnv> {
     pub cw_ave: CwAve<Chain>,
     pub cw_ave_factory: CwAveFactory<Chain>,
     pub cw420: Cw420<Chain>,
+    pub cw721_poap: Cw721Poap<Chain>,
 }
 
 impl<Chain: CwEnv> CwAveSuite<Chain> {
@@ -18,6 +19,7 @@ impl<Chain: CwEnv> CwAveSuite<Chain> {
             cw_ave: CwAve::new(chain.clone()),
             cw_ave_factory: CwAveFactory::new(chain.clone()),
             cw420: Cw420::new(chain.clone()),
+            cw721_poap: Cw721Poap::new(chain.clone()),
         }
     }
 }
@@ -31,15 +33,18 @@ impl<Chain: CwEnv> Deploy<Chain> for CwAveSuite<Chain> {
         let cw_ave = CwAve::new(chain.clone());
         let cw_ave_factory = CwAveFactory::new(chain.clone());
         let cw420 = Cw420::new(chain.clone());
+        let cw721_poap = Cw721Poap::new(chain.clone());
 
         cw_ave.upload()?;
         cw_ave_factory.upload()?;
         cw420.upload()?;
+        cw721_poap.upload()?;
 
         Ok(Self {
             cw_ave,
             cw_ave_factory,
             cw420,
+            cw721_poap,
         })
     }
 
@@ -1,15 +1,40 @@
-use av_event_helpers::get_license_fee;
-use cosmwasm_std::{coin, coins, Timestamp};
-use cw4::Member;
-use cw_ave::msg::{ExecuteMsg, InstantiateMsg, QueryMsgFns};
+use av_event_helpers::{get_license_addr, get_license_fee};
+use cosmos_sdk_proto::{
+    cosmos::crypto::{ed25519, secp256k1, secp256r1},
+    traits::Message,
+    Any,
+};
+use cosmwasm_std::{
+    coin, coins, testing::MockApi, to_json_binary, Api, Binary, Timestamp, Uint128,
+};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20Coin, Cw20ExecuteMsg};
+use cw20_base::msg::{InstantiateMsg as Cw20InstantiateMsg, QueryMsg as Cw20BaseQueryMsg};
+use cw4::{Member, MemberResponse};
+use cw420::msg::QueryMsg as Cw420QueryMsg;
+use cw721_poap::msg::{
+    AllNftInfoResponse, ExecuteMsg as Cw721PoapExecuteMsg, NumTokensResponse, PoapMetadata,
+    QueryMsg as Cw721PoapQueryMsg,
+};
+use cw_ave::checkin::{decode_pubkey, pubkey_to_canonical, verify_checkin_signature};
+use cw_ave::escrow::REFUND_WINDOW;
+use cw_ave::msg::{
+    EscrowStatusResponse, EventSegmentRes, ExecuteMsg, GuestTypeUpdate, InstantiateMsg,
+    OccupancyRes, PayeeRevenueRes, QueryMsg, QueryMsgFns, ReceiveMsg, ResaleListingRes,
+    SegmentSchedule, StatusResponse, TicketAllowanceRes, TicketReceiptRes, WaitlistEntryRes,
+};
 use cw_ave::state::{
-    Config, EventSegment, EventSegmentAccessType, GuestDetails, RegisteringEventAddressAndPayment,
-    RegisteringGuest,
+    preamble_msg_arb_036, AttendanceRecord, BatchCheckInMode, CheckInDetails, CheckInLogEntry,
+    CheckInRecord, CheckInSignatureData, CheckInSignatureScheme, CheckOutRecord, Config,
+    EventPhase, EventSegment, EventSegmentAccessType, GroupMode, GuestDetails, HomieCheckinSecret,
+    PoapSettings, ReentryRule, RegisteringEventAddressAndPayment, RegisteringGuest,
+    ReplaceHomieTicket, ResaleSettings, RevenueShare, RevenueSplit, TicketReceipt, UsherScope,
 };
 use cw_ave_factory::msg::{ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInitMsg};
 use cw_orch::{anyhow, prelude::*};
+use k256::ecdsa::signature::Signer;
+use sha2::{Digest, Sha256};
 
-use crate::interfaces::CwAveSuite;
+use crate::interfaces::{Cw20Base, Cw420, Cw721Poap, CwAveSuite};
 
 /// INIT UNIT TESTS
 // calling contract with funds:
@@ -47,7 +72,7 @@ use crate::interfaces::CwAveSuite;
 /// EVENT SEGMENT ACCESS
 /// - single - works normal
 /// - single - errors on incorrect segment for a guest
-/// 
+///
 
 struct TestEnv<Env: CwEnv> {
     mock: Env,
@@ -57,8 +82,34 @@ struct TestEnv<Env: CwEnv> {
 impl TestEnv<MockBech32> {
     /// Set up the test environment with an Account that has the Standalone installed
     fn setup() -> anyhow::Result<TestEnv<MockBech32>> {
+        Self::setup_with(default_event)
+    }
+
+    /// Set up the test environment with custom guest details & event timeline,
+    /// built relative to the block time the event is created at.
+    fn setup_with(
+        event: impl FnOnce(Timestamp) -> (Vec<GuestDetails>, Vec<EventSegment>),
+    ) -> anyhow::Result<TestEnv<MockBech32>> {
         // Create a sender and mock env
-        let chain = MockBech32::new_with_chain_id("mock", "juno-1");
+        Self::setup_on(MockBech32::new_with_chain_id("mock", "juno-1"), event)
+    }
+
+    /// Set up the test environment on an existing chain, for events depending on other contracts.
+    fn setup_on(
+        chain: MockBech32,
+        event: impl FnOnce(Timestamp) -> (Vec<GuestDetails>, Vec<EventSegment>),
+    ) -> anyhow::Result<TestEnv<MockBech32>> {
+        Self::setup_custom(chain, |now, msg| {
+            (msg.guest_details, msg.event_timeline) = event(now);
+        })
+    }
+
+    /// Set up the test environment with any changes to the event instantiate message,
+    /// built relative to the block time the event is created at.
+    fn setup_custom(
+        chain: MockBech32,
+        customize: impl FnOnce(Timestamp, &mut InstantiateMsg),
+    ) -> anyhow::Result<TestEnv<MockBech32>> {
         chain.set_balance(&chain.sender_addr(), vec![coin(1000000000000, "ujuno")])?;
         let suite = CwAveSuite::deploy_on(chain.clone(), ())?;
 
@@ -72,25 +123,8 @@ impl TestEnv<MockBech32> {
             &[get_license_fee(&chain.env_info().chain_id)?],
         )?;
 
-        // Create sample guest details
-        let guest_details = vec![GuestDetails {
-            guest_type: "VIP".to_string(),
-            guest_weight: 1,
-            max_ticket_limit: 5,
-            total_ticket_limit: 100,
-            ticket_cost: vec![coin(1000000, "ujuno")],
-            event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
-        }];
-
-        // Create sample event timeline
-        let event_timeline = vec![EventSegment {
-            stage_description: "Main Event".to_string(),
-            start: Timestamp::from_seconds(1000),
-            end: Timestamp::from_seconds(2000),
-        }];
-
         // Instantiate the cw-ave contract
-        let instantiate_msg = InstantiateMsg {
+        let mut instantiate_msg = InstantiateMsg {
             event_curator: chain.sender_addr().to_string(),
             title: "Test Event".to_string(),
             description: "Test Description".to_string(),
@@ -98,10 +132,19 @@ impl TestEnv<MockBech32> {
                 addr: chain.sender_addr().to_string(),
                 weight: 1,
             }],
-            guest_details,
+            guest_details: vec![],
             cw420: suite.cw420.code_id()?,
-            event_timeline,
+            resale: None,
+            existing_usher_group: None,
+            usher_supervisor_weight: None,
+            existing_guest_group: None,
+            poap: None,
+            draft: false,
+            revenue_splits: vec![],
+            webauthn_rp_id: None,
+            event_timeline: vec![],
         };
+        customize(chain.block_info()?.time, &mut instantiate_msg);
 
         let cw_ave_addr = suite
             .cw_ave_factory
@@ -121,6 +164,186 @@ impl TestEnv<MockBech32> {
     }
 }
 
+/// a single VIP guest type with access to a single segment, starting in 1000 seconds
+fn default_event(now: Timestamp) -> (Vec<GuestDetails>, Vec<EventSegment>) {
+    // Create sample guest details
+    let guest_details = vec![GuestDetails {
+        guest_type: "VIP".to_string(),
+        guest_weight: 1,
+        max_ticket_limit: 5,
+        total_ticket_limit: 100,
+        ticket_cost: vec![coin(1000000, "ujuno")],
+        cw20_ticket_cost: vec![],
+        overbooking_limit: 0,
+        reentry: ReentryRule::Never,
+        event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
+    }];
+
+    // Create sample event timeline
+    let event_timeline = vec![EventSegment {
+        stage_description: "Main Event".to_string(),
+        start: now.plus_seconds(1000),
+        end: now.plus_seconds(2000),
+        escrow_weight: None,
+        early_checkin_grace: None,
+        late_checkin_grace: None,
+        capacity: None,
+    }];
+    (guest_details, event_timeline)
+}
+
+/// key of a ticket address, for each supported checkin signature scheme
+enum GuestSigner {
+    Secp256k1(k256::ecdsa::SigningKey),
+    Ed25519(ed25519_zebra::SigningKey),
+    Passkey(p256::ecdsa::SigningKey),
+}
+
+/// key of a ticket address, signing ADR-036 checkin data
+struct GuestKey {
+    signer: GuestSigner,
+    addr: Addr,
+}
+
+impl GuestKey {
+    fn new(seed: u8) -> Self {
+        Self::from_signer(GuestSigner::Secp256k1(
+            k256::ecdsa::SigningKey::from_slice(&[seed; 32]).unwrap(),
+        ))
+    }
+
+    fn ed25519(seed: u8) -> Self {
+        Self::from_signer(GuestSigner::Ed25519(ed25519_zebra::SigningKey::from(
+            [seed; 32],
+        )))
+    }
+
+    fn passkey(seed: u8) -> Self {
+        Self::from_signer(GuestSigner::Passkey(
+            p256::ecdsa::SigningKey::from_slice(&[seed; 32]).unwrap(),
+        ))
+    }
+
+    fn from_signer(signer: GuestSigner) -> Self {
+        let scheme = match signer {
+            GuestSigner::Secp256k1(_) => CheckInSignatureScheme::Adr036Secp256k1 {},
+            GuestSigner::Ed25519(_) => CheckInSignatureScheme::Adr036Ed25519 {},
+            GuestSigner::Passkey(_) => CheckInSignatureScheme::WebAuthnSecp256r1 {
+                authenticator_data: Binary::default(),
+                client_data_json: String::new(),
+            },
+        };
+        let canonical = pubkey_to_canonical(&Self::key_bytes(&signer), &scheme);
+        let addr = MockApi::default()
+            .with_prefix("mock")
+            .addr_humanize(&canonical)
+            .unwrap();
+        GuestKey { signer, addr }
+    }
+
+    fn key_bytes(signer: &GuestSigner) -> Vec<u8> {
+        match signer {
+            GuestSigner::Secp256k1(key) => key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+            GuestSigner::Ed25519(key) => {
+                ed25519_zebra::VerificationKey::from(key).as_ref().to_vec()
+            }
+            GuestSigner::Passkey(key) => key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        }
+    }
+
+    fn pubkey(&self) -> Binary {
+        let key = Self::key_bytes(&self.signer);
+        let (type_url, value) = match self.signer {
+            GuestSigner::Secp256k1(_) => (
+                "/cosmos.crypto.secp256k1.PubKey",
+                secp256k1::PubKey { key }.encode_to_vec(),
+            ),
+            GuestSigner::Ed25519(_) => (
+                "/cosmos.crypto.ed25519.PubKey",
+                ed25519::PubKey { key }.encode_to_vec(),
+            ),
+            GuestSigner::Passkey(_) => (
+                "/cosmos.crypto.secp256r1.PubKey",
+                secp256r1::PubKey { key }.encode_to_vec(),
+            ),
+        };
+        Any {
+            type_url: type_url.to_string(),
+            value,
+        }
+        .encode_to_vec()
+        .into()
+    }
+
+    /// checkin details for `ticket_addr`, signed with this key
+    fn sign_for(&self, ticket_addr: &Addr, data: &CheckInSignatureData) -> CheckInDetails {
+        let signed_data = to_json_binary(data).unwrap().to_base64();
+        let sign_doc = preamble_msg_arb_036(ticket_addr.as_str(), &signed_data);
+        let (signature, scheme) = match &self.signer {
+            GuestSigner::Secp256k1(key) => {
+                let signature: k256::ecdsa::Signature = key.sign(sign_doc.as_bytes());
+                (signature.to_bytes().to_vec(), None)
+            }
+            GuestSigner::Ed25519(key) => (
+                key.sign(sign_doc.as_bytes()).to_bytes().to_vec(),
+                Some(CheckInSignatureScheme::Adr036Ed25519 {}),
+            ),
+            GuestSigner::Passkey(key) => {
+                let client_data_json = format!(
+                    r#"{{"type":"webauthn.get","challenge":"{}","origin":"https://ave.example","crossOrigin":false}}"#,
+                    base64url(&Sha256::digest(sign_doc.as_bytes()))
+                );
+                // rp id hash, flags (user present & verified) & signature counter
+                let mut authenticator_data = Sha256::digest(WEBAUTHN_RP_ID).to_vec();
+                authenticator_data.extend([0x05, 0, 0, 0, 7]);
+                let mut signed = authenticator_data.clone();
+                signed.extend(Sha256::digest(client_data_json.as_bytes()));
+                let signature: p256::ecdsa::Signature = key.sign(&signed);
+                let signature = signature.normalize_s().unwrap_or(signature);
+                (
+                    signature.to_bytes().to_vec(),
+                    Some(CheckInSignatureScheme::WebAuthnSecp256r1 {
+                        authenticator_data: authenticator_data.into(),
+                        client_data_json,
+                    }),
+                )
+            }
+        };
+        CheckInDetails {
+            signature: signature.into(),
+            signed_data,
+            ticket_addr: ticket_addr.to_string(),
+            pubkey: self.pubkey(),
+            scheme,
+            gate: None,
+            supervisor_override: false,
+        }
+    }
+
+    fn checkin(&self, data: &CheckInSignatureData) -> CheckInDetails {
+        self.sign_for(&self.addr, data)
+    }
+}
+
+/// relying party passkeys sign checkins for in tests
+const WEBAUTHN_RP_ID: &str = "ave.example";
+
+fn base64url(data: &[u8]) -> String {
+    Binary::from(data)
+        .to_base64()
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
 #[test]
 fn test_successful_instantiate() -> anyhow::Result<()> {
     let env = TestEnv::setup()?;
@@ -146,6 +369,9 @@ fn test_duplicate_guest_weight_fails() -> anyhow::Result<()> {
             max_ticket_limit: 5,
             total_ticket_limit: 100,
             ticket_cost: vec![coin(1000000, "ujuno")],
+            cw20_ticket_cost: vec![],
+            overbooking_limit: 0,
+            reentry: ReentryRule::Never,
             event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
         },
         GuestDetails {
@@ -154,6 +380,9 @@ fn test_duplicate_guest_weight_fails() -> anyhow::Result<()> {
             max_ticket_limit: 10,
             total_ticket_limit: 500,
             ticket_cost: vec![coin(500000, "ujuno")],
+            cw20_ticket_cost: vec![],
+            overbooking_limit: 0,
+            reentry: ReentryRule::Never,
             event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
         },
     ];
@@ -162,6 +391,10 @@ fn test_duplicate_guest_weight_fails() -> anyhow::Result<()> {
         stage_description: "Main Event".to_string(),
         start: Timestamp::from_seconds(1000),
         end: Timestamp::from_seconds(2000),
+        escrow_weight: None,
+        early_checkin_grace: None,
+        late_checkin_grace: None,
+        capacity: None,
     }];
 
     let instantiate_msg = InstantiateMsg {
@@ -174,6 +407,14 @@ fn test_duplicate_guest_weight_fails() -> anyhow::Result<()> {
         }],
         guest_details,
         cw420: t.suite.cw420.code_id()?,
+        resale: None,
+        existing_usher_group: None,
+        usher_supervisor_weight: None,
+        existing_guest_group: None,
+        poap: None,
+        draft: false,
+        revenue_splits: vec![],
+        webauthn_rp_id: None,
         event_timeline,
     };
 
@@ -199,6 +440,9 @@ fn test_invalid_event_dates_fails() -> anyhow::Result<()> {
         max_ticket_limit: 5,
         total_ticket_limit: 100,
         ticket_cost: vec![coin(1000000, "ujuno")],
+        cw20_ticket_cost: vec![],
+        overbooking_limit: 0,
+        reentry: ReentryRule::Never,
         event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
     }];
 
@@ -207,6 +451,10 @@ fn test_invalid_event_dates_fails() -> anyhow::Result<()> {
         stage_description: "Main Event".to_string(),
         start: Timestamp::from_seconds(2000),
         end: Timestamp::from_seconds(1000), // End before start
+        escrow_weight: None,
+        early_checkin_grace: None,
+        late_checkin_grace: None,
+        capacity: None,
     }];
 
     let instantiate_msg = InstantiateMsg {
@@ -219,6 +467,14 @@ fn test_invalid_event_dates_fails() -> anyhow::Result<()> {
         }],
         guest_details,
         cw420: t.suite.cw420.code_id()?,
+        resale: None,
+        existing_usher_group: None,
+        usher_supervisor_weight: None,
+        existing_guest_group: None,
+        poap: None,
+        draft: false,
+        revenue_splits: vec![],
+        webauthn_rp_id: None,
         event_timeline,
     };
 
@@ -244,6 +500,9 @@ fn test_overlapping_event_dates_fails() -> anyhow::Result<()> {
         max_ticket_limit: 5,
         total_ticket_limit: 100,
         ticket_cost: vec![coin(1000000, "ujuno")],
+        cw20_ticket_cost: vec![],
+        overbooking_limit: 0,
+        reentry: ReentryRule::Never,
         event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
     }];
 
@@ -253,11 +512,19 @@ fn test_overlapping_event_dates_fails() -> anyhow::Result<()> {
             stage_description: "Event 1".to_string(),
             start: Timestamp::from_seconds(1000),
             end: Timestamp::from_seconds(2000),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
         },
         EventSegment {
             stage_description: "Event 2".to_string(),
             start: Timestamp::from_seconds(1500), // Overlaps with previous event
             end: Timestamp::from_seconds(2500),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
         },
     ];
 
@@ -271,6 +538,14 @@ fn test_overlapping_event_dates_fails() -> anyhow::Result<()> {
         }],
         guest_details,
         cw420: t.suite.cw420.code_id()?,
+        resale: None,
+        existing_usher_group: None,
+        usher_supervisor_weight: None,
+        existing_guest_group: None,
+        poap: None,
+        draft: false,
+        revenue_splits: vec![],
+        webauthn_rp_id: None,
         event_timeline,
     };
 
@@ -342,7 +617,7 @@ fn test_purchase_tickets_insufficient_funds() -> anyhow::Result<()> {
 
 #[test]
 fn test_claim_ticket_payments_curator_only() -> anyhow::Result<()> {
-    let mut t = TestEnv::setup()?;
+    let t = TestEnv::setup()?;
 
     // Try to claim as curator (should succeed)
     let result = t
@@ -354,11 +629,11 @@ fn test_claim_ticket_payments_curator_only() -> anyhow::Result<()> {
 
     // Try to claim as different user (should fail)
     let other_user = t.mock.addr_make("other_user");
-    t.mock.set_sender(other_user);
 
     let result = t
         .suite
         .cw_ave
+        .call_as(&other_user)
         .execute(&ExecuteMsg::ClaimTicketPayments {}, &[]);
 
     assert!(result.is_err());
@@ -396,3 +671,2934 @@ fn test_query_functions() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_escrow_released_per_event_segment() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let guest_details = vec![GuestDetails {
+            guest_type: "Weekend".to_string(),
+            guest_weight: 1,
+            max_ticket_limit: 5,
+            total_ticket_limit: 100,
+            ticket_cost: vec![coin(1000000, "ujuno")],
+            cw20_ticket_cost: vec![],
+            overbooking_limit: 0,
+            reentry: ReentryRule::Never,
+            event_segment_access: EventSegmentAccessType::AllOfSpecificSegments { ids: vec![0, 1] },
+        }];
+        let event_timeline = vec![
+            EventSegment {
+                stage_description: "Day 1".to_string(),
+                start: now.plus_seconds(1000),
+                end: now.plus_seconds(2000),
+                escrow_weight: Some(1),
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
+            },
+            EventSegment {
+                stage_description: "Day 2".to_string(),
+                start: now.plus_seconds(3000),
+                end: now.plus_seconds(4000),
+                escrow_weight: Some(3),
+                early_checkin_grace: None,
+                late_checkin_grace: None,
+                capacity: None,
+            },
+        ];
+        (guest_details, event_timeline)
+    })?;
+    let curator = t.mock.sender_addr();
+    let buyer = t
+        .mock
+        .addr_make_with_balance("buyer", coins(1000000, "ujuno"))?;
+
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: vec![RegisteringEventAddressAndPayment {
+                    ticket_addr: buyer.to_string(),
+                    payment_asset: "ujuno".to_string(),
+                }],
+            }],
+        },
+        &coins(1000000, "ujuno"),
+    )?;
+
+    // 3% dev fee is never escrowed, everything else is locked until segments end
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert!(status.claimable.is_empty());
+    assert_eq!(status.locked, coins(970000, "ujuno"));
+
+    // nothing to claim before the first segment ends
+    let balance = t.mock.query_balance(&curator, "ujuno")?;
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(t.mock.query_balance(&curator, "ujuno")?, balance);
+
+    // first segment ended: 1/4 of the escrow is released
+    t.mock.wait_seconds(2000)?;
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.claimable, coins(242500, "ujuno"));
+    assert_eq!(status.locked, coins(727500, "ujuno"));
+
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(242500)
+    );
+
+    // claiming again within the same segment pays nothing more
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(242500)
+    );
+
+    // second segment ended: the rest is released
+    t.mock.wait_seconds(2000)?;
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.claimable, coins(727500, "ujuno"));
+    assert!(status.locked.is_empty());
+    assert_eq!(status.claimed, coins(242500, "ujuno"));
+
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(970000)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_event_lifecycle() -> anyhow::Result<()> {
+    let chain = MockBech32::new_with_chain_id("mock", "juno-1");
+    let t = TestEnv::setup_custom(chain, |now, msg| {
+        (msg.guest_details, msg.event_timeline) = default_event(now);
+        msg.draft = true;
+    })?;
+    let curator = t.mock.sender_addr();
+    let segments: Vec<EventSegmentRes> = t.suite.cw_ave.query(&QueryMsg::EventSegments {})?;
+    let (start, end) = (segments[0].segment.start, segments[0].segment.end);
+    let status = |t: &TestEnv<MockBech32>| -> anyhow::Result<StatusResponse> {
+        Ok(t.suite.cw_ave.query(&QueryMsg::Status {})?)
+    };
+
+    // drafts are not on sale until the curator opens sales
+    assert_eq!(
+        status(&t)?,
+        StatusResponse {
+            phase: EventPhase::Draft,
+            next_phase: Some(EventPhase::OnSale),
+            next_transition: None,
+        }
+    );
+    let guest = GuestKey::new(1);
+    let err = purchase_tickets(&t, &[&guest.addr]).unwrap_err();
+    assert!(format!("{:?}", err).contains("while the event is draft"));
+
+    // only the curator opens sales, settles the event & claims its revenue
+    let stranger = t.mock.addr_make("stranger");
+    for msg in [
+        ExecuteMsg::OpenSales {},
+        ExecuteMsg::SettleEvent {},
+        ExecuteMsg::ClaimTicketPayments {},
+    ] {
+        let err = t
+            .suite
+            .cw_ave
+            .call_as(&stranger)
+            .execute(&msg, &[])
+            .unwrap_err();
+        assert!(format!("{:?}", err).contains("only the event curator"));
+    }
+    t.suite.cw_ave.execute(&ExecuteMsg::OpenSales {}, &[])?;
+    assert_eq!(
+        status(&t)?,
+        StatusResponse {
+            phase: EventPhase::OnSale,
+            next_phase: Some(EventPhase::Live),
+            next_transition: Some(start),
+        }
+    );
+    purchase_tickets(&t, &[&guest.addr])?;
+
+    // only ended events can be settled
+    let err = t
+        .suite
+        .cw_ave
+        .execute(&ExecuteMsg::SettleEvent {}, &[])
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("while the event is on sale"));
+
+    // tickets are still sold while the event is live
+    t.mock.wait_seconds(1000)?;
+    assert_eq!(
+        status(&t)?,
+        StatusResponse {
+            phase: EventPhase::Live,
+            next_phase: Some(EventPhase::Ended),
+            next_transition: Some(end),
+        }
+    );
+    let late = GuestKey::new(2);
+    purchase_tickets(&t, &[&late.addr])?;
+
+    // but not once it ended, while late checkins & claims are still allowed
+    t.mock.wait_seconds(1000)?;
+    assert_eq!(
+        status(&t)?,
+        StatusResponse {
+            phase: EventPhase::Ended,
+            next_phase: Some(EventPhase::Settled),
+            next_transition: None,
+        }
+    );
+    let err = purchase_tickets(&t, &[&t.mock.addr_make("after")]).unwrap_err();
+    assert!(format!("{:?}", err).contains("while the event is ended"));
+
+    // settling pays out all ticket revenue
+    let balance = t.mock.query_balance(&curator, "ujuno")?;
+    t.suite.cw_ave.execute(&ExecuteMsg::SettleEvent {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(1940000)
+    );
+    assert_eq!(status(&t)?.phase, EventPhase::Settled);
+
+    // settled events no longer accept checkins or claims
+    let err = t
+        .suite
+        .cw_ave
+        .execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("while the event is settled"));
+    let res = t
+        .suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_event_cancellation_refunds() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let guest_details = vec![GuestDetails {
+            guest_type: "Weekend".to_string(),
+            guest_weight: 1,
+            max_ticket_limit: 5,
+            total_ticket_limit: 100,
+            ticket_cost: vec![coin(1000000, "ujuno")],
+            cw20_ticket_cost: vec![],
+            overbooking_limit: 0,
+            reentry: ReentryRule::Never,
+            event_segment_access: EventSegmentAccessType::AllOfSpecificSegments { ids: vec![0, 1] },
+        }];
+        let segment = |description: &str, start: u64, escrow_weight: u64| EventSegment {
+            stage_description: description.to_string(),
+            start: now.plus_seconds(start),
+            end: now.plus_seconds(start + 1000),
+            escrow_weight: Some(escrow_weight),
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
+        };
+        (
+            guest_details,
+            vec![segment("Day 1", 1000, 1), segment("Day 2", 3000, 3)],
+        )
+    })?;
+    let curator = t.mock.sender_addr();
+    let [alice, bob] = [t.mock.addr_make("alice"), t.mock.addr_make("bob")];
+    purchase_tickets_as(&t, &alice, &[&alice])?;
+    purchase_tickets_as(&t, &bob, &[&bob])?;
+    let claim_refund = |holder: &Addr, ticket: &Addr| {
+        t.suite.cw_ave.call_as(holder).execute(
+            &ExecuteMsg::ClaimCancellationRefunds {
+                tickets: vec![ticket.to_string()],
+            },
+            &[],
+        )
+    };
+
+    // only the curator or the factory owner can cancel the event
+    let stranger = t.mock.addr_make("stranger");
+    let err = t
+        .suite
+        .cw_ave
+        .call_as(&stranger)
+        .execute(
+            &ExecuteMsg::CancelEvent {
+                segment_ids: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("can cancel it"));
+
+    // cancelling day 2 sets aside its 3/4 of the escrow for refunds
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CancelEvent {
+            segment_ids: vec![1],
+        },
+        &[],
+    )?;
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.refundable, coins(1455000, "ujuno"));
+    assert_eq!(status.locked, coins(485000, "ujuno"));
+    let deadline = status.refund_deadline.unwrap();
+    let phase: StatusResponse = t.suite.cw_ave.query(&QueryMsg::Status {})?;
+    assert_eq!(phase.phase, EventPhase::OnSale);
+
+    // holders pull their share once, other wallets cannot pull it for them
+    let err = claim_refund(&stranger, &alice).unwrap_err();
+    assert!(format!("{:?}", err).contains("holder of this ticket"));
+    claim_refund(&alice, &alice)?;
+    assert_eq!(t.mock.query_balance(&alice, "ujuno")?, Uint128::new(727500));
+    claim_refund(&alice, &alice)?;
+    assert_eq!(t.mock.query_balance(&alice, "ujuno")?, Uint128::new(727500));
+
+    // tickets with cancelled segments are no longer refunded in full
+    let err = t
+        .suite
+        .cw_ave
+        .call_as(&bob)
+        .execute(
+            &ExecuteMsg::RefundUnconfirmedTickets {
+                guests: vec![bob.to_string()],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("claim its cancellation refund"));
+    // nor are tickets with cancelled segments sold
+    let err = purchase_tickets(&t, &[&stranger]).unwrap_err();
+    assert!(format!("{:?}", err).contains("no longer sold"));
+
+    // cancelling the whole event refunds the rest, freezing claims & sales
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CancelEvent {
+            segment_ids: vec![],
+        },
+        &[],
+    )?;
+    let phase: StatusResponse = t.suite.cw_ave.query(&QueryMsg::Status {})?;
+    assert_eq!(phase.phase, EventPhase::Cancelled);
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.refundable, coins(1212500, "ujuno"));
+    assert!(status.locked.is_empty());
+    assert!(status.refund_deadline.unwrap() >= deadline);
+
+    let err = t
+        .suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("refunds can still be claimed"));
+    let err = purchase_tickets(&t, &[&stranger]).unwrap_err();
+    assert!(format!("{:?}", err).contains("while the event is cancelled"));
+
+    claim_refund(&bob, &bob)?;
+    assert_eq!(t.mock.query_balance(&bob, "ujuno")?, Uint128::new(970000));
+
+    // refunds left unclaimed go to the curator once the refund window closes
+    t.mock.wait_seconds(REFUND_WINDOW)?;
+    let err = claim_refund(&alice, &alice).unwrap_err();
+    assert!(format!("{:?}", err).contains("refund window has closed"));
+    let balance = t.mock.query_balance(&curator, "ujuno")?;
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(242500)
+    );
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert!(status.claimable.is_empty() && status.refundable.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_refund_windows_per_cancellation() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let (mut guest_details, mut event_timeline) = default_event(now);
+        guest_details[0].event_segment_access =
+            EventSegmentAccessType::AllOfSpecificSegments { ids: vec![0, 1] };
+        // the second segment starts after the refund window of the first closes
+        let start = now.plus_seconds(REFUND_WINDOW + 1000);
+        event_timeline.push(EventSegment {
+            stage_description: "Day 2".to_string(),
+            start,
+            end: start.plus_seconds(1000),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
+        });
+        (guest_details, event_timeline)
+    })?;
+    let curator = t.mock.sender_addr();
+    let [alice, bob] = [t.mock.addr_make("alice"), t.mock.addr_make("bob")];
+    purchase_tickets_as(&t, &alice, &[&alice])?;
+    purchase_tickets_as(&t, &bob, &[&bob])?;
+    let claim_refund = |holder: &Addr| {
+        t.suite.cw_ave.call_as(holder).execute(
+            &ExecuteMsg::ClaimCancellationRefunds {
+                tickets: vec![holder.to_string()],
+            },
+            &[],
+        )
+    };
+    let cancel = |segment_ids: Vec<u64>| {
+        t.suite
+            .cw_ave
+            .execute(&ExecuteMsg::CancelEvent { segment_ids }, &[])
+    };
+
+    // only alice claims the refund of day 1, the rest is swept to the curator once its window closes
+    cancel(vec![0])?;
+    claim_refund(&alice)?;
+    assert_eq!(t.mock.query_balance(&alice, "ujuno")?, Uint128::new(485000));
+    t.mock.wait_seconds(REFUND_WINDOW)?;
+    let balance = t.mock.query_balance(&curator, "ujuno")?;
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(485000)
+    );
+    let err = claim_refund(&bob).unwrap_err();
+    assert!(format!("{:?}", err).contains("refund window has closed"));
+
+    // cancelling day 2 opens its own window, refunding only its share of each ticket
+    cancel(vec![])?;
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.refundable, coins(970000, "ujuno"));
+    claim_refund(&bob)?;
+    assert_eq!(t.mock.query_balance(&bob, "ujuno")?, Uint128::new(485000));
+    claim_refund(&alice)?;
+    assert_eq!(t.mock.query_balance(&alice, "ujuno")?, Uint128::new(970000));
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert!(status.refundable.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_revenue_splits() -> anyhow::Result<()> {
+    let chain = MockBech32::new_with_chain_id("mock", "juno-1");
+    let [venue, artist] = [chain.addr_make("venue"), chain.addr_make("artist")];
+    let splits = vec![
+        RevenueSplit {
+            payee: venue.clone(),
+            share: RevenueShare::Bps { bps: 2000 },
+        },
+        RevenueSplit {
+            payee: artist.clone(),
+            share: RevenueShare::PerTicket {
+                guest_weight: 1,
+                amount: coins(100000, "ujuno"),
+            },
+        },
+    ];
+
+    // basis points cannot add up to more than all revenue, nor be paid per ticket in denoms not accepted
+    for invalid in [
+        vec![
+            splits[0].clone(),
+            RevenueSplit {
+                payee: artist.clone(),
+                share: RevenueShare::Bps { bps: 8001 },
+            },
+        ],
+        vec![RevenueSplit {
+            payee: artist.clone(),
+            share: RevenueShare::PerTicket {
+                guest_weight: 1,
+                amount: coins(100000, "uatom"),
+            },
+        }],
+    ] {
+        let res = TestEnv::setup_custom(chain.clone(), |now, msg| {
+            (msg.guest_details, msg.event_timeline) = default_event(now);
+            msg.revenue_splits = invalid;
+        });
+        assert!(res.is_err());
+    }
+
+    let t = TestEnv::setup_custom(chain, |now, msg| {
+        (msg.guest_details, msg.event_timeline) = default_event(now);
+        msg.revenue_splits = splits;
+    })?;
+    let curator = t.mock.sender_addr();
+    let [alice, bob] = [t.mock.addr_make("alice"), t.mock.addr_make("bob")];
+    purchase_tickets(&t, &[&alice, &bob])?;
+    let withdraw = |payee: &Addr| {
+        t.suite.cw_ave.call_as(payee).execute(
+            &ExecuteMsg::WithdrawRevenueShare {
+                denom: "ujuno".to_string(),
+            },
+            &[],
+        )
+    };
+
+    // guest types cannot stop accepting the denoms shares are paid per ticket in
+    let reprice = |ticket_cost: Vec<Coin>| ExecuteMsg::UpdateEvent {
+        title: None,
+        description: None,
+        add_guest_types: vec![],
+        update_guest_types: vec![GuestTypeUpdate {
+            guest_weight: 1,
+            total_ticket_limit: None,
+            max_ticket_limit: None,
+            ticket_cost: Some(ticket_cost),
+            cw20_ticket_cost: None,
+        }],
+        reschedule_segments: vec![],
+    };
+    let err = t
+        .suite
+        .cw_ave
+        .execute(&reprice(coins(1000000, "uatom")), &[])
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("revenue split"));
+    t.suite.cw_ave.execute(
+        &reprice(vec![coin(1000000, "ujuno"), coin(1000000, "uatom")]),
+        &[],
+    )?;
+
+    // nothing accrues before the event segment ends
+    let revenue: PayeeRevenueRes = t.suite.cw_ave.query(&QueryMsg::PayeeRevenue {
+        payee: venue.to_string(),
+    })?;
+    assert!(revenue.accrued.is_empty());
+    withdraw(&venue)?;
+    assert_eq!(t.mock.query_balance(&venue, "ujuno")?, Uint128::zero());
+
+    // shares per ticket come first, then basis points of the rest, leaving the curator what is left
+    t.mock.wait_seconds(2000)?;
+    let revenue: Vec<PayeeRevenueRes> = t.suite.cw_ave.query(&QueryMsg::AllPayeeRevenue {})?;
+    assert_eq!(
+        revenue,
+        vec![
+            PayeeRevenueRes {
+                payee: venue.clone(),
+                accrued: coins(348000, "ujuno"),
+                withdrawn: vec![],
+            },
+            PayeeRevenueRes {
+                payee: artist.clone(),
+                accrued: coins(200000, "ujuno"),
+                withdrawn: vec![],
+            },
+        ]
+    );
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.claimable, coins(1392000, "ujuno"));
+
+    // payees withdraw their own share, once
+    let stranger = t.mock.addr_make("stranger");
+    let err = withdraw(&stranger).unwrap_err();
+    assert!(format!("{:?}", err).contains("not a payee"));
+    withdraw(&venue)?;
+    withdraw(&venue)?;
+    assert_eq!(t.mock.query_balance(&venue, "ujuno")?, Uint128::new(348000));
+    let revenue: PayeeRevenueRes = t.suite.cw_ave.query(&QueryMsg::PayeeRevenue {
+        payee: venue.to_string(),
+    })?;
+    assert_eq!(revenue.withdrawn, coins(348000, "ujuno"));
+
+    // independently of the curator claiming theirs
+    let balance = t.mock.query_balance(&curator, "ujuno")?;
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        balance + Uint128::new(1392000)
+    );
+    withdraw(&artist)?;
+    assert_eq!(
+        t.mock.query_balance(&artist, "ujuno")?,
+        Uint128::new(200000)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_refund_unconfirmed_tickets() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let buyer = t
+        .mock
+        .addr_make_with_balance("buyer", coins(2000000, "ujuno"))?;
+    let homie = t.mock.addr_make("homie");
+
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: vec![
+                    RegisteringEventAddressAndPayment {
+                        ticket_addr: buyer.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    },
+                    RegisteringEventAddressAndPayment {
+                        ticket_addr: homie.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    },
+                ],
+            }],
+        },
+        &coins(2000000, "ujuno"),
+    )?;
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.locked, coins(1940000, "ujuno"));
+
+    // only the purchaser or curator can refund a ticket
+    let stranger = t.mock.addr_make("stranger");
+    let res = t.suite.cw_ave.call_as(&stranger).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![homie.to_string()],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // purchaser is refunded the ticket price minus the dev fee
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![homie.to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(t.mock.query_balance(&buyer, "ujuno")?, Uint128::new(970000));
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.locked, coins(970000, "ujuno"));
+
+    // a ticket cannot be refunded twice
+    let res = t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![homie.to_string()],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // curator can refund on behalf of the purchaser
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![buyer.to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(
+        t.mock.query_balance(&buyer, "ujuno")?,
+        Uint128::new(1940000)
+    );
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert!(status.locked.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_refund_closed_after_segment_ends() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let buyer = t
+        .mock
+        .addr_make_with_balance("buyer", coins(1000000, "ujuno"))?;
+
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: vec![RegisteringEventAddressAndPayment {
+                    ticket_addr: buyer.to_string(),
+                    payment_asset: "ujuno".to_string(),
+                }],
+            }],
+        },
+        &coins(1000000, "ujuno"),
+    )?;
+
+    t.mock.wait_seconds(2000)?;
+    let res = t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![buyer.to_string()],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    assert_eq!(t.mock.query_balance(&buyer, "ujuno")?, Uint128::zero());
+
+    Ok(())
+}
+
+#[test]
+fn test_ticket_receipts() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let buyer = t
+        .mock
+        .addr_make_with_balance("buyer", coins(2000000, "ujuno"))?;
+    let homie = t.mock.addr_make("homie");
+
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: vec![
+                    RegisteringEventAddressAndPayment {
+                        ticket_addr: buyer.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    },
+                    RegisteringEventAddressAndPayment {
+                        ticket_addr: homie.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    },
+                ],
+            }],
+        },
+        &coins(2000000, "ujuno"),
+    )?;
+
+    let receipt: TicketReceipt = t.suite.cw_ave.query(&QueryMsg::TicketReceipt {
+        ticket_addr: homie.to_string(),
+    })?;
+    assert_eq!(
+        receipt,
+        TicketReceipt {
+            purchaser: buyer.clone(),
+            guest_weight: 1,
+            denom: "ujuno".to_string(),
+            price: Uint128::new(1000000),
+            dev_fee: Uint128::new(30000),
+            purchased_at: t.mock.block_info()?.time,
+        }
+    );
+
+    let by_purchaser: Vec<TicketReceiptRes> =
+        t.suite.cw_ave.query(&QueryMsg::TicketReceiptsByPurchaser {
+            purchaser: buyer.to_string(),
+            start_after: None,
+            limit: None,
+        })?;
+    assert_eq!(by_purchaser.len(), 2);
+    assert!(by_purchaser.iter().all(|r| r.receipt.purchaser == buyer));
+
+    // paginate through receipts of a guest type
+    let first: Vec<TicketReceiptRes> =
+        t.suite.cw_ave.query(&QueryMsg::TicketReceiptsByGuestType {
+            guest_weight: 1,
+            start_after: None,
+            limit: Some(1),
+        })?;
+    assert_eq!(first.len(), 1);
+    let rest: Vec<TicketReceiptRes> =
+        t.suite.cw_ave.query(&QueryMsg::TicketReceiptsByGuestType {
+            guest_weight: 1,
+            start_after: Some(first[0].ticket_addr.clone()),
+            limit: None,
+        })?;
+    assert_eq!(rest.len(), 1);
+    assert_ne!(first[0].ticket_addr, rest[0].ticket_addr);
+
+    // nobody else has receipts
+    let none: Vec<TicketReceiptRes> =
+        t.suite.cw_ave.query(&QueryMsg::TicketReceiptsByPurchaser {
+            purchaser: homie.to_string(),
+            start_after: None,
+            limit: None,
+        })?;
+    assert!(none.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_purchase_adds_paid_tickets_to_guest_list() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let buyer = t
+        .mock
+        .addr_make_with_balance("buyer", coins(2500000, "ujuno"))?;
+    let homie = t.mock.addr_make("homie");
+    let license_addr = get_license_addr(&t.mock.env_info().chain_id)?;
+
+    // enough funds for a single ticket, the second entry is left unpaid
+    let res = t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: vec![
+                    RegisteringEventAddressAndPayment {
+                        ticket_addr: buyer.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    },
+                    RegisteringEventAddressAndPayment {
+                        ticket_addr: homie.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    },
+                ],
+            }],
+        },
+        &coins(1500000, "ujuno"),
+    )?;
+
+    let config: Config = t.suite.cw_ave.config()?;
+    let guest_group = Cw420::new(t.mock.clone());
+    guest_group.set_address(&config.event_guest_contract);
+
+    let member: MemberResponse = guest_group.query(&Cw420QueryMsg::Member {
+        addr: buyer.to_string(),
+        at_height: None,
+    })?;
+    assert_eq!(member.weight, Some(1));
+    let member: MemberResponse = guest_group.query(&Cw420QueryMsg::Member {
+        addr: homie.to_string(),
+        at_height: None,
+    })?;
+    assert_eq!(member.weight, None);
+
+    // change is returned and the dev fee is paid out
+    assert_eq!(
+        t.mock.query_balance(&buyer, "ujuno")?,
+        Uint128::new(1500000)
+    );
+    assert!(res.events.iter().any(|e| e.ty == "transfer"
+        && e.attributes
+            .iter()
+            .any(|a| a.key == "recipient" && a.value == license_addr.as_str())
+        && e.attributes
+            .iter()
+            .any(|a| a.key == "amount" && a.value == "30000ujuno")));
+
+    // the same ticket address cannot be purchased twice
+    let res = t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: vec![RegisteringEventAddressAndPayment {
+                    ticket_addr: buyer.to_string(),
+                    payment_asset: "ujuno".to_string(),
+                }],
+            }],
+        },
+        &coins(1000000, "ujuno"),
+    );
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_existing_usher_and_guest_groups() -> anyhow::Result<()> {
+    let chain = MockBech32::new_with_chain_id("mock", "juno-1");
+    let guest = GuestKey::new(1);
+    let stranger = chain.addr_make("stranger");
+
+    // groups administered by the curator, whose members the event cannot update
+    let groups = Cw420::new(chain.clone());
+    groups.upload()?;
+    let group_init = |addr: &Addr| cw420::msg::InstantiateMsg {
+        admin: Some(chain.sender_addr().to_string()),
+        members: vec![Member {
+            addr: addr.to_string(),
+            weight: 1,
+        }],
+    };
+    groups.instantiate(&group_init(&chain.sender_addr()), None, &[])?;
+    let usher_group = groups.address()?;
+    groups.instantiate(&group_init(&guest.addr), None, &[])?;
+    let guest_group = groups.address()?;
+
+    // usher members cannot be set alongside an existing usher group
+    let res = TestEnv::setup_custom(chain.clone(), |now, msg| {
+        (msg.guest_details, msg.event_timeline) = default_event(now);
+        msg.existing_usher_group = Some(usher_group.to_string());
+    });
+    assert!(res.is_err());
+    // existing groups must be queryable as cw4 groups
+    let res = TestEnv::setup_custom(chain.clone(), |now, msg| {
+        (msg.guest_details, msg.event_timeline) = default_event(now);
+        msg.existing_guest_group = Some(stranger.to_string());
+    });
+    assert!(res.is_err());
+
+    let t = TestEnv::setup_custom(chain, |now, msg| {
+        (msg.guest_details, msg.event_timeline) = default_event(now);
+        msg.usher_admins = vec![];
+        msg.existing_usher_group = Some(usher_group.to_string());
+        msg.existing_guest_group = Some(guest_group.to_string());
+    })?;
+    let config: Config = t.suite.cw_ave.config()?;
+    assert_eq!(config.event_usher_contract, usher_group);
+    assert_eq!(config.event_guest_contract, guest_group);
+    assert_eq!(config.usher_group, GroupMode::Existing { writable: false });
+    assert_eq!(config.guest_group, GroupMode::Existing { writable: false });
+
+    // tickets can only be purchased for members of the guest group
+    assert!(purchase_tickets(&t, &[&stranger]).is_err());
+    let buyer = purchase_tickets(&t, &[&guest.addr])?;
+
+    // tickets cannot move to addresses outside of the guest group
+    let res = t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::UpdateTicketAddress {
+            new_ticket_addr: None,
+            replace_homies_ticket: vec![ReplaceHomieTicket {
+                old: guest.addr.to_string(),
+                new: stranger.to_string(),
+            }],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let member: MemberResponse = groups.query(&Cw420QueryMsg::Member {
+        addr: stranger.to_string(),
+        at_height: None,
+    })?;
+    assert_eq!(member.weight, None);
+
+    // ushers are managed through the existing usher group
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::AddUshers {
+            ushers: vec![Member {
+                addr: stranger.to_string(),
+                weight: 1,
+            }],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // members of the existing usher group checkin guests
+    t.mock.wait_seconds(1000)?;
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: guest.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(checked_in);
+
+    Ok(())
+}
+
+#[test]
+fn test_purchase_tickets_with_cw20() -> anyhow::Result<()> {
+    // the license address is only valid for the juno prefix, which cw20 transfers validate
+    let chain = MockBech32::new_with_chain_id("juno", "juno-1");
+    let buyer = chain.addr_make("buyer");
+    let homie = chain.addr_make("homie");
+
+    let token = Cw20Base::new(chain.clone());
+    token.upload()?;
+    let cw20_init = |name: &str, symbol: &str, amount: u128| Cw20InstantiateMsg {
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+            address: buyer.to_string(),
+            amount: Uint128::new(amount),
+        }],
+        mint: None,
+        marketing: None,
+    };
+    token.instantiate(&cw20_init("Other Token", "OTHER", 1000000), None, &[])?;
+    let other_token = token.address()?;
+    token.instantiate(&cw20_init("Ticket Token", "TIX", 3000000), None, &[])?;
+
+    let token_addr = token.address()?;
+    let t = TestEnv::setup_on(chain, |now| {
+        let guest_details = vec![GuestDetails {
+            guest_type: "VIP".to_string(),
+            guest_weight: 1,
+            max_ticket_limit: 5,
+            total_ticket_limit: 100,
+            ticket_cost: vec![coin(1000000, "ujuno")],
+            cw20_ticket_cost: vec![Cw20Coin {
+                address: token_addr.to_string(),
+                amount: Uint128::new(500000),
+            }],
+            overbooking_limit: 0,
+            reentry: ReentryRule::Never,
+            event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
+        }];
+        let event_timeline = vec![EventSegment {
+            stage_description: "Main Event".to_string(),
+            start: now.plus_seconds(1000),
+            end: now.plus_seconds(2000),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
+        }];
+        (guest_details, event_timeline)
+    })?;
+    let cw_ave = t.suite.cw_ave.address()?;
+    let balance = |addr: &Addr| -> anyhow::Result<Uint128> {
+        let res: Cw20BalanceResponse = token.query(&Cw20BaseQueryMsg::Balance {
+            address: addr.to_string(),
+        })?;
+        Ok(res.balance)
+    };
+
+    let payment_options = t.suite.cw_ave.ticket_payment_options_by_guest_weight(1)?;
+    assert_eq!(payment_options.payment_options, coins(1000000, "ujuno"));
+    assert_eq!(
+        payment_options.cw20_payment_options,
+        vec![Cw20Coin {
+            address: token_addr.to_string(),
+            amount: Uint128::new(500000),
+        }]
+    );
+
+    let purchase = to_json_binary(&ReceiveMsg::PurchaseTickets {
+        guests: vec![RegisteringGuest {
+            guest_weight: 1,
+            reap: [&buyer, &homie]
+                .iter()
+                .map(|addr| RegisteringEventAddressAndPayment {
+                    ticket_addr: addr.to_string(),
+                    payment_asset: token_addr.to_string(),
+                })
+                .collect(),
+        }],
+    })?;
+
+    // tokens not accepted for tickets are rejected
+    let res = t.mock.call_as(&buyer).execute(
+        &Cw20ExecuteMsg::Send {
+            contract: cw_ave.to_string(),
+            amount: Uint128::new(1000000),
+            msg: purchase.clone(),
+        },
+        &[],
+        &other_token,
+    );
+    assert!(res.is_err());
+
+    // overpaying returns the change, and the dev fee is paid in the token
+    token.call_as(&buyer).execute(
+        &Cw20ExecuteMsg::Send {
+            contract: cw_ave.to_string(),
+            amount: Uint128::new(1200000),
+            msg: purchase,
+        },
+        &[],
+    )?;
+    assert_eq!(balance(&buyer)?, Uint128::new(2000000));
+    assert_eq!(
+        balance(&get_license_addr(&t.mock.env_info().chain_id)?)?,
+        Uint128::new(30000)
+    );
+    assert_eq!(balance(&cw_ave)?, Uint128::new(970000));
+
+    let receipt: TicketReceipt = t.suite.cw_ave.ticket_receipt(homie.to_string())?;
+    assert_eq!(receipt.denom, token_addr.to_string());
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.locked, coins(970000, token_addr.to_string()));
+
+    // refunds are returned in the token paid
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![homie.to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(balance(&buyer)?, Uint128::new(2485000));
+
+    // released escrow is claimed in the token paid
+    t.mock.wait_seconds(2000)?;
+    t.suite
+        .cw_ave
+        .execute(&ExecuteMsg::ClaimTicketPayments {}, &[])?;
+    assert_eq!(balance(&t.mock.sender_addr())?, Uint128::new(485000));
+    assert_eq!(balance(&cw_ave)?, Uint128::zero());
+
+    Ok(())
+}
+
+#[test]
+fn test_waitlist_for_sold_out_guest_type() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let guest_details = vec![GuestDetails {
+            guest_type: "VIP".to_string(),
+            guest_weight: 1,
+            max_ticket_limit: 0,
+            total_ticket_limit: 1,
+            ticket_cost: vec![coin(1000000, "ujuno")],
+            cw20_ticket_cost: vec![],
+            overbooking_limit: 2,
+            reentry: ReentryRule::Never,
+            event_segment_access: EventSegmentAccessType::SingleSegment { id: 0 },
+        }];
+        let event_timeline = vec![EventSegment {
+            stage_description: "Main Event".to_string(),
+            start: now.plus_seconds(1000),
+            end: now.plus_seconds(2000),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
+        }];
+        (guest_details, event_timeline)
+    })?;
+    let license_addr = get_license_addr(&t.mock.env_info().chain_id)?;
+    let first = t.mock.addr_make("first");
+    purchase_tickets_as(&t, &first, &[&first])?;
+    let buyer = t
+        .mock
+        .addr_make_with_balance("buyer", coins(3000000, "ujuno"))?;
+    let [b1, b2, b3] = ["b1", "b2", "b3"].map(|name| t.mock.addr_make(name));
+    let waitlist = || -> anyhow::Result<Vec<WaitlistEntryRes>> {
+        Ok(t.suite.cw_ave.query(&QueryMsg::Waitlist {
+            guest_weight: 1,
+            start_after: None,
+            limit: None,
+        })?)
+    };
+
+    // sold out: the first entries join the waitlist until it is full, the rest are rejected
+    let res = t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: [&b1, &b2, &b3]
+                    .iter()
+                    .map(|addr| RegisteringEventAddressAndPayment {
+                        ticket_addr: addr.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    })
+                    .collect(),
+            }],
+        },
+        &coins(3000000, "ujuno"),
+    )?;
+    assert_eq!(
+        res.event_attr_values("wasm", "waitlisted"),
+        vec![b1.to_string(), b2.to_string()]
+    );
+    assert_eq!(
+        res.event_attr_values("wasm", "sold_out"),
+        vec![b3.to_string()]
+    );
+    // the full price is held, no dev fee is paid until promoted
+    assert_eq!(
+        t.mock.query_balance(&buyer, "ujuno")?,
+        Uint128::new(1000000)
+    );
+    assert!(!res.events.iter().any(|e| e.ty == "transfer"
+        && e.attributes
+            .iter()
+            .any(|a| a.key == "recipient" && a.value == license_addr.as_str())));
+
+    let entries = waitlist()?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].entry.ticket_addr, b1.to_string());
+    assert_eq!(entries[0].ahead, 0);
+    assert_eq!(entries[1].entry.ticket_addr, b2.to_string());
+    assert_eq!(entries[1].ahead, 1);
+    assert_eq!(entries[1].entry.receipt.purchaser, buyer);
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.locked, coins(970000, "ujuno"));
+
+    // once the waitlist is full, purchases are rejected and their funds returned
+    let late = t.mock.addr_make("late");
+    purchase_tickets_as(&t, &late, &[&late])?;
+    assert_eq!(t.mock.query_balance(&late, "ujuno")?, Uint128::new(1000000));
+    assert_eq!(waitlist()?.len(), 2);
+
+    // only the purchaser can withdraw from the waitlist, for the full price paid
+    let res = t.suite.cw_ave.call_as(&first).execute(
+        &ExecuteMsg::WithdrawFromWaitlist {
+            tickets: vec![b2.to_string()],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::WithdrawFromWaitlist {
+            tickets: vec![b2.to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(
+        t.mock.query_balance(&buyer, "ujuno")?,
+        Uint128::new(2000000)
+    );
+    assert!(t
+        .suite
+        .cw_ave
+        .query::<WaitlistEntryRes>(&QueryMsg::WaitlistEntry {
+            ticket_addr: b2.to_string(),
+        })
+        .is_err());
+
+    // the next purchase joins the end of the waitlist
+    let c1 = t.mock.addr_make("c1");
+    purchase_tickets_as(&t, &c1, &[&c1])?;
+    let entry: WaitlistEntryRes = t.suite.cw_ave.query(&QueryMsg::WaitlistEntry {
+        ticket_addr: c1.to_string(),
+    })?;
+    assert_eq!(entry.ahead, 1);
+
+    // a refunded ticket is given to the first in line
+    let res = t.suite.cw_ave.call_as(&first).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![first.to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(
+        res.event_attr_values("wasm", "promoted"),
+        vec![b1.to_string()]
+    );
+    assert!(res.events.iter().any(|e| e.ty == "transfer"
+        && e.attributes
+            .iter()
+            .any(|a| a.key == "recipient" && a.value == license_addr.as_str())
+        && e.attributes
+            .iter()
+            .any(|a| a.key == "amount" && a.value == "30000ujuno")));
+
+    let receipt: TicketReceipt = t.suite.cw_ave.query(&QueryMsg::TicketReceipt {
+        ticket_addr: b1.to_string(),
+    })?;
+    assert_eq!(receipt.purchaser, buyer);
+    let config: Config = t.suite.cw_ave.config()?;
+    let guest_group = Cw420::new(t.mock.clone());
+    guest_group.set_address(&config.event_guest_contract);
+    let member: MemberResponse = guest_group.query(&Cw420QueryMsg::Member {
+        addr: b1.to_string(),
+        at_height: None,
+    })?;
+    assert_eq!(member.weight, Some(1));
+    let status: EscrowStatusResponse = t.suite.cw_ave.escrow_status()?;
+    assert_eq!(status.locked, coins(970000, "ujuno"));
+
+    let entries = waitlist()?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].entry.ticket_addr, c1.to_string());
+    assert_eq!(entries[0].ahead, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_ticket_limit_per_wallet() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let buyer = t.mock.addr_make("buyer");
+    let homies: Vec<Addr> = (0..4)
+        .map(|i| t.mock.addr_make(format!("homie{i}")))
+        .collect();
+    let allowance = |wallet: &Addr| -> anyhow::Result<TicketAllowanceRes> {
+        let res: Vec<TicketAllowanceRes> = t.suite.cw_ave.query(&QueryMsg::TicketAllowance {
+            purchaser: wallet.to_string(),
+        })?;
+        Ok(res[0].clone())
+    };
+    assert_eq!(allowance(&buyer)?.remaining, Some(5));
+
+    // tickets reserved for homies count against the purchaser
+    let mut tickets = vec![&buyer];
+    tickets.extend(homies.iter().take(2));
+    purchase_tickets_as(&t, &buyer, &tickets)?;
+    let res = allowance(&buyer)?;
+    assert_eq!(res.reserved, 3);
+    assert_eq!(res.remaining, Some(2));
+
+    purchase_tickets_as(&t, &buyer, &[&homies[2], &homies[3]])?;
+    assert_eq!(allowance(&buyer)?.remaining, Some(0));
+    let extra = t.mock.addr_make("extra");
+    assert!(purchase_tickets_as(&t, &buyer, &[&extra]).is_err());
+
+    // other wallets keep their own allowance
+    let other = t.mock.addr_make("other");
+    purchase_tickets_as(&t, &other, &[&other, &extra])?;
+    assert_eq!(allowance(&other)?.remaining, Some(3));
+
+    // refunded tickets go back to the purchaser's allowance
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![homies[0].to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(allowance(&buyer)?.remaining, Some(1));
+
+    Ok(())
+}
+
+/// purchases a ticket of the default guest type for each ticket address
+fn purchase_tickets(t: &TestEnv<MockBech32>, tickets: &[&Addr]) -> anyhow::Result<Addr> {
+    let buyer = t.mock.addr_make("buyer");
+    purchase_tickets_as(t, &buyer, tickets)?;
+    Ok(buyer)
+}
+
+/// purchases a ticket of the default guest type for each ticket address, paid for by `buyer`
+fn purchase_tickets_as(
+    t: &TestEnv<MockBech32>,
+    buyer: &Addr,
+    tickets: &[&Addr],
+) -> anyhow::Result<()> {
+    let cost = 1000000 * tickets.len() as u128;
+    t.mock.add_balance(buyer, coins(cost, "ujuno"))?;
+    t.suite.cw_ave.call_as(buyer).execute(
+        &ExecuteMsg::PurchaseTickets {
+            guests: vec![RegisteringGuest {
+                guest_weight: 1,
+                reap: tickets
+                    .iter()
+                    .map(|addr| RegisteringEventAddressAndPayment {
+                        ticket_addr: addr.to_string(),
+                        payment_asset: "ujuno".to_string(),
+                    })
+                    .collect(),
+            }],
+        },
+        &coins(cost, "ujuno"),
+    )?;
+    Ok(())
+}
+
+/// checkin data for the default usher, valid for the next 5 minutes
+fn checkin_data(
+    t: &TestEnv<MockBech32>,
+    event_segment_ids: Vec<u64>,
+    nonce: u64,
+) -> CheckInSignatureData {
+    CheckInSignatureData {
+        event_contract_addr: t.suite.cw_ave.address().unwrap().to_string(),
+        event_segment_ids,
+        homies_tickets: vec![],
+        usher_wallet_addr: t.mock.sender_addr().to_string(),
+        nonce,
+        expires_at: t.mock.block_info().unwrap().time.plus_seconds(300),
+    }
+}
+
+#[test]
+fn test_checkin_guest() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let guest = GuestKey::new(1);
+    purchase_tickets(&t, &[&guest.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    // attendance records exist for each segment once purchased
+    let attendance: Vec<(u64, bool)> =
+        t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatusAll {
+            guest: guest.addr.to_string(),
+        })?;
+    assert_eq!(attendance, vec![(0, false)]);
+
+    // only ushers can checkin guests
+    let stranger = t.mock.addr_make("stranger");
+    let res = t.suite.cw_ave.call_as(&stranger).execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 2)),
+        },
+        &[],
+    )?;
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: guest.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(checked_in);
+
+    // a guest cannot checkin twice
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 3)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_curator_updates_event() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let (mut guest_details, event_timeline) = default_event(now);
+        guest_details[0].total_ticket_limit = 1;
+        guest_details[0].max_ticket_limit = 0;
+        guest_details[0].overbooking_limit = 1;
+        (guest_details, event_timeline)
+    })?;
+    let now = t.mock.block_info()?.time;
+    let update = |msg: ExecuteMsg| t.suite.cw_ave.execute(&msg, &[]);
+    let retitle = |title: &str, description: Option<&str>| ExecuteMsg::UpdateEvent {
+        title: Some(title.to_string()),
+        description: description.map(String::from),
+        add_guest_types: vec![],
+        update_guest_types: vec![],
+        reschedule_segments: vec![],
+    };
+    let update_vip = |update: GuestTypeUpdate| ExecuteMsg::UpdateEvent {
+        title: None,
+        description: None,
+        add_guest_types: vec![],
+        update_guest_types: vec![update],
+        reschedule_segments: vec![],
+    };
+    let vip = GuestTypeUpdate {
+        guest_weight: 1,
+        total_ticket_limit: None,
+        max_ticket_limit: None,
+        ticket_cost: None,
+        cw20_ticket_cost: None,
+    };
+
+    // only the curator can update the event
+    let stranger = t.mock.addr_make("stranger");
+    let res = t
+        .suite
+        .cw_ave
+        .call_as(&stranger)
+        .execute(&retitle("Hijacked", None), &[]);
+    assert!(res.is_err());
+    update(retitle("Rescheduled Event", Some("Now with more stages")))?;
+    let config: Config = t.suite.cw_ave.config()?;
+    assert_eq!(config.title, "Rescheduled Event");
+    assert_eq!(config.description, "Now with more stages");
+    assert!(update(retitle(&"x".repeat(129), None)).is_err());
+
+    // the only ticket is sold, the next purchase joins the waitlist
+    let [first, second] = [GuestKey::new(1), GuestKey::new(2)];
+    purchase_tickets(&t, &[&first.addr])?;
+    purchase_tickets(&t, &[&second.addr])?;
+    let err = update(update_vip(GuestTypeUpdate {
+        total_ticket_limit: Some(0),
+        ..vip.clone()
+    }))
+    .unwrap_err();
+    assert!(format!("{:?}", err).contains("already sold"));
+
+    // raising the ticket limit promotes the waitlist
+    let res = update(update_vip(GuestTypeUpdate {
+        total_ticket_limit: Some(3),
+        ticket_cost: Some(coins(2000000, "ujuno")),
+        ..vip.clone()
+    }))?;
+    assert_eq!(
+        res.event_attr_values("wasm", "promoted"),
+        vec![second.addr.to_string()]
+    );
+    let receipt: TicketReceipt = t.suite.cw_ave.query(&QueryMsg::TicketReceipt {
+        ticket_addr: second.addr.to_string(),
+    })?;
+    assert_eq!(receipt.price, Uint128::new(1000000));
+
+    // tickets sold from now on cost the new price, the old one buys none
+    let third = t.mock.addr_make("third");
+    purchase_tickets(&t, &[&third])?;
+    let receipt: Result<TicketReceipt, _> = t.suite.cw_ave.query(&QueryMsg::TicketReceipt {
+        ticket_addr: third.to_string(),
+    });
+    assert!(receipt.is_err());
+    let gd: GuestDetails = t
+        .suite
+        .cw_ave
+        .query(&QueryMsg::GuestTypeDetailsByWeight { guest_weight: 1 })?;
+    assert_eq!(gd.total_ticket_limit, 3);
+    assert_eq!(gd.ticket_cost, coins(2000000, "ujuno"));
+
+    // new guest types are validated as when the event was created
+    let (mut staff, _) = default_event(now);
+    staff[0].guest_type = "Staff".to_string();
+    let add = |gd: GuestDetails| ExecuteMsg::UpdateEvent {
+        title: None,
+        description: None,
+        add_guest_types: vec![gd],
+        update_guest_types: vec![],
+        reschedule_segments: vec![],
+    };
+    assert!(update(add(staff[0].clone())).is_err());
+    staff[0].guest_weight = 2;
+    assert!(update(add(GuestDetails {
+        event_segment_access: EventSegmentAccessType::SingleSegment { id: 1 },
+        ..staff[0].clone()
+    }))
+    .is_err());
+    update(add(staff[0].clone()))?;
+    let all: Vec<GuestDetails> = t.suite.cw_ave.query(&QueryMsg::GuestTypeDetailsAll {})?;
+    assert_eq!(all.len(), 2);
+
+    // segments can be moved until they start, keeping a valid timeline
+    let reschedule = |start: u64, end: u64| ExecuteMsg::UpdateEvent {
+        title: None,
+        description: None,
+        add_guest_types: vec![],
+        update_guest_types: vec![],
+        reschedule_segments: vec![SegmentSchedule {
+            segment_id: 0,
+            start: now.plus_seconds(start),
+            end: now.plus_seconds(end),
+        }],
+    };
+    assert!(update(reschedule(3000, 2000)).is_err());
+    update(reschedule(2000, 3000))?;
+    let segments: Vec<EventSegmentRes> = t.suite.cw_ave.query(&QueryMsg::EventSegments {})?;
+    assert_eq!(segments[0].segment.start, now.plus_seconds(2000));
+    t.mock.wait_seconds(2000)?;
+    let err = update(reschedule(4000, 5000)).unwrap_err();
+    assert!(format!("{:?}", err).contains("before they start"));
+
+    // events cannot be edited once they ended
+    t.mock.wait_seconds(1001)?;
+    let err = update(retitle("Encore", None)).unwrap_err();
+    assert!(format!("{:?}", err).contains("ended"));
+
+    Ok(())
+}
+
+#[test]
+fn test_curator_manages_ushers() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let curator = t.mock.sender_addr();
+    let usher = t.mock.addr_make("usher");
+    let stranger = t.mock.addr_make("stranger");
+    let [guest, late_guest] = [GuestKey::new(1), GuestKey::new(2)];
+    purchase_tickets(&t, &[&guest.addr, &late_guest.addr])?;
+    let member = |addr: &Addr, weight: u64| Member {
+        addr: addr.to_string(),
+        weight,
+    };
+    let ushers = || -> anyhow::Result<Vec<Member>> {
+        Ok(t.suite.cw_ave.query(&QueryMsg::Ushers {
+            start_after: None,
+            limit: None,
+        })?)
+    };
+    let usher_checkin = |guest: &GuestKey, nonce: u64| {
+        t.suite.cw_ave.call_as(&usher).execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: guest.checkin(&CheckInSignatureData {
+                    usher_wallet_addr: usher.to_string(),
+                    ..checkin_data(&t, vec![0], nonce)
+                }),
+            },
+            &[],
+        )
+    };
+
+    // only the curator manages ushers
+    let add_usher = ExecuteMsg::AddUshers {
+        ushers: vec![member(&usher, 1)],
+    };
+    assert!(t
+        .suite
+        .cw_ave
+        .call_as(&stranger)
+        .execute(&add_usher, &[])
+        .is_err());
+    t.suite.cw_ave.execute(&add_usher, &[])?;
+    let mut expected = vec![member(&curator, 1), member(&usher, 1)];
+    expected.sort_by(|a, b| a.addr.cmp(&b.addr));
+    assert_eq!(ushers()?, expected);
+    // existing ushers are updated by weight, not added again
+    assert!(t.suite.cw_ave.execute(&add_usher, &[]).is_err());
+
+    // added ushers can checkin guests
+    t.mock.wait_seconds(1000)?;
+    usher_checkin(&guest, 1)?;
+
+    // only existing ushers have their weight updated
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::UpdateUsherWeights {
+            ushers: vec![member(&stranger, 2)],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::UpdateUsherWeights {
+            ushers: vec![member(&usher, 2)],
+        },
+        &[],
+    )?;
+    assert!(ushers()?.contains(&member(&usher, 2)));
+
+    // removed ushers can no longer checkin guests
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::RemoveUshers {
+            ushers: vec![stranger.to_string()],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::RemoveUshers {
+            ushers: vec![usher.to_string()],
+        },
+        &[],
+    )?;
+    assert_eq!(ushers()?, vec![member(&curator, 1)]);
+    assert!(usher_checkin(&late_guest, 1).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_usher_scopes_and_supervisors() -> anyhow::Result<()> {
+    let chain = MockBech32::new_with_chain_id("mock", "juno-1");
+    let supervisor = chain.sender_addr();
+    let usher = chain.addr_make("usher");
+    let t = TestEnv::setup_custom(chain, |now, msg| {
+        let (guest_details, mut event_timeline) = default_event(now);
+        event_timeline.push(EventSegment {
+            stage_description: "After Party".to_string(),
+            start: now.plus_seconds(2000),
+            end: now.plus_seconds(3000),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: None,
+        });
+        (msg.guest_details, msg.event_timeline) = (guest_details, event_timeline);
+        msg.usher_admins = vec![
+            Member {
+                addr: supervisor.to_string(),
+                weight: 10,
+            },
+            Member {
+                addr: usher.to_string(),
+                weight: 1,
+            },
+        ];
+        msg.usher_supervisor_weight = Some(10);
+    })?;
+    let [north, south, late] = [GuestKey::new(1), GuestKey::new(2), GuestKey::new(3)];
+    purchase_tickets(&t, &[&north.addr, &south.addr, &late.addr])?;
+    let set_scope = |sender: &Addr, usher: &Addr, scope: Option<UsherScope>| {
+        t.suite.cw_ave.call_as(sender).execute(
+            &ExecuteMsg::SetUsherScope {
+                usher: usher.to_string(),
+                scope,
+            },
+            &[],
+        )
+    };
+    let scope = |segment_ids: Vec<u64>, gates: Vec<&str>| UsherScope {
+        segment_ids,
+        gates: gates.into_iter().map(String::from).collect(),
+    };
+    let checkin = |sender: &Addr, guest: &GuestKey, gate: Option<&str>, supervisor_override| {
+        t.suite.cw_ave.call_as(sender).execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: CheckInDetails {
+                    gate: gate.map(String::from),
+                    supervisor_override,
+                    ..guest.checkin(&CheckInSignatureData {
+                        usher_wallet_addr: sender.to_string(),
+                        ..checkin_data(&t, vec![0], 1)
+                    })
+                },
+            },
+            &[],
+        )
+    };
+
+    // only the curator scopes ushers, to existing event segments
+    assert!(set_scope(&usher, &usher, Some(scope(vec![0], vec![]))).is_err());
+    assert!(set_scope(&supervisor, &t.mock.addr_make("stranger"), None).is_err());
+    assert!(set_scope(&supervisor, &usher, Some(scope(vec![5], vec![]))).is_err());
+    set_scope(&supervisor, &usher, Some(scope(vec![1], vec!["north"])))?;
+    let res: Option<UsherScope> = t.suite.cw_ave.query(&QueryMsg::UsherScope {
+        usher: usher.to_string(),
+    })?;
+    assert_eq!(res, Some(scope(vec![1], vec!["north"])));
+
+    // scoped ushers only checkin their segments, at their gates
+    t.mock.wait_seconds(1000)?;
+    assert!(checkin(&usher, &north, Some("north"), false).is_err());
+    set_scope(&supervisor, &usher, Some(scope(vec![0], vec!["north"])))?;
+    assert!(checkin(&usher, &north, None, false).is_err());
+    assert!(checkin(&usher, &north, Some("south"), false).is_err());
+    let res = checkin(&usher, &north, Some("north"), false)?;
+    assert_eq!(res.event_attr_value("wasm", "gate")?, "north");
+
+    // supervisors are not restricted by scopes
+    set_scope(
+        &supervisor,
+        &supervisor,
+        Some(scope(vec![1], vec!["north"])),
+    )?;
+    checkin(&supervisor, &south, Some("south"), false)?;
+
+    // only supervisors can override checkin windows
+    t.mock.wait_seconds(2000)?;
+    assert!(checkin(&usher, &late, Some("north"), true).is_err());
+    assert!(checkin(&supervisor, &late, None, false).is_err());
+    checkin(&supervisor, &late, None, true)?;
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: late.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(checked_in);
+
+    // removing a scope lets the usher checkin anywhere again
+    set_scope(&supervisor, &usher, None)?;
+    let res: Option<UsherScope> = t.suite.cw_ave.query(&QueryMsg::UsherScope {
+        usher: usher.to_string(),
+    })?;
+    assert_eq!(res, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_attendance_records() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let usher = t.mock.sender_addr();
+    let [first, absent, second] = [GuestKey::new(1), GuestKey::new(2), GuestKey::new(3)];
+    purchase_tickets(&t, &[&first.addr, &absent.addr, &second.addr])?;
+    let mut checked_in = [&first, &second].map(|guest| guest.addr.to_string());
+    checked_in.sort();
+
+    t.mock.wait_seconds(1000)?;
+    let block = t.mock.block_info()?;
+    for (guest, gate) in [(&first, Some("north")), (&second, None)] {
+        t.suite.cw_ave.execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: CheckInDetails {
+                    gate: gate.map(String::from),
+                    ..guest.checkin(&checkin_data(&t, vec![0], 1))
+                },
+            },
+            &[],
+        )?;
+    }
+
+    // checkins are recorded with the block, usher & gate
+    let records: Vec<AttendanceRecord> =
+        t.suite.cw_ave.query(&QueryMsg::GuestAttendanceRecords {
+            guest: first.addr.to_string(),
+        })?;
+    assert_eq!(
+        records,
+        vec![AttendanceRecord {
+            ticket_addr: first.addr.to_string(),
+            segment_id: 0,
+            checked_in: true,
+            checkin: Some(CheckInRecord {
+                time: block.time,
+                height: block.height,
+                usher: usher.clone(),
+                gate: Some("north".to_string()),
+            }),
+            checked_out: false,
+            reentries: 0,
+            checkout: None,
+        }]
+    );
+    let status: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: absent.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(!status);
+
+    // attendees of a segment are paginated by ticket address, skipping those not checked in
+    let attendees = |start_after: Option<String>| -> anyhow::Result<Vec<String>> {
+        let res: Vec<AttendanceRecord> = t.suite.cw_ave.query(&QueryMsg::SegmentAttendees {
+            segment_id: 0,
+            start_after,
+            limit: Some(1),
+        })?;
+        Ok(res.into_iter().map(|r| r.ticket_addr).collect())
+    };
+    assert_eq!(attendees(None)?, vec![checked_in[0].clone()]);
+    assert_eq!(
+        attendees(Some(checked_in[0].clone()))?,
+        vec![checked_in[1].clone()]
+    );
+    assert!(attendees(Some(checked_in[1].clone()))?.is_empty());
+
+    // as is the checkin history of an usher
+    let history: Vec<CheckInLogEntry> = t.suite.cw_ave.query(&QueryMsg::UsherCheckins {
+        usher: usher.to_string(),
+        start_after: Some((checked_in[0].clone(), 0, 0)),
+        limit: None,
+    })?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].ticket_addr, checked_in[1]);
+
+    Ok(())
+}
+
+#[test]
+fn test_occupancy_of_segments_not_open() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let (mut guest_details, mut event_timeline) = default_event(now);
+        guest_details[0].event_segment_access =
+            EventSegmentAccessType::AllOfSpecificSegments { ids: vec![0, 1] };
+        event_timeline.push(EventSegment {
+            stage_description: "Day 2".to_string(),
+            start: event_timeline[0].end.plus_seconds(1000),
+            end: event_timeline[0].end.plus_seconds(2000),
+            escrow_weight: None,
+            early_checkin_grace: None,
+            late_checkin_grace: None,
+            capacity: Some(1),
+        });
+        (guest_details, event_timeline)
+    })?;
+    let [first, second] = [GuestKey::new(1), GuestKey::new(2)];
+    purchase_tickets(&t, &[&first.addr, &second.addr])?;
+    t.mock.wait_seconds(1000)?;
+    let occupancy = || -> anyhow::Result<Vec<u32>> {
+        let res: Vec<OccupancyRes> = t.suite.cw_ave.query(&QueryMsg::Occupancy {})?;
+        Ok(res.into_iter().map(|o| o.occupancy).collect())
+    };
+
+    let checkin = |guest: &GuestKey, nonce: u64| {
+        t.suite.cw_ave.execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: guest.checkin(&checkin_data(&t, vec![0, 1], nonce)),
+            },
+            &[],
+        )
+    };
+
+    // guests checked in to every segment at once are only inside the segments that opened,
+    // but count towards the capacity of those that have not
+    checkin(&first, 1)?;
+    assert_eq!(occupancy()?, vec![1, 0]);
+    let err = checkin(&second, 1).unwrap_err();
+    assert!(format!("{:?}", err).contains("at capacity"));
+
+    // guests leaving before a segment opens are not counted once it does
+    t.suite.cw_ave.call_as(&first.addr).execute(
+        &ExecuteMsg::CheckOut {
+            event_segment_ids: vec![0, 1],
+        },
+        &[],
+    )?;
+    checkin(&second, 2)?;
+    assert_eq!(occupancy()?, vec![1, 0]);
+    let segments: Vec<EventSegmentRes> = t.suite.cw_ave.query(&QueryMsg::EventSegments {})?;
+    let opens = segments
+        .iter()
+        .find(|s| s.seg_id == 1)
+        .unwrap()
+        .segment
+        .start;
+    t.mock
+        .wait_seconds(opens.seconds() - t.mock.block_info()?.time.seconds())?;
+    assert_eq!(occupancy()?, vec![1, 1]);
+    t.suite.cw_ave.call_as(&second.addr).execute(
+        &ExecuteMsg::CheckOut {
+            event_segment_ids: vec![1],
+        },
+        &[],
+    )?;
+    assert_eq!(occupancy()?, vec![1, 0]);
+
+    Ok(())
+}
+
+#[test]
+fn test_checkout_reentry_and_capacity() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        let (mut guest_details, mut event_timeline) = default_event(now);
+        guest_details[0].reentry = ReentryRule::Limited { max: 1 };
+        event_timeline[0].capacity = Some(2);
+        (guest_details, event_timeline)
+    })?;
+    let usher = t.mock.sender_addr();
+    let [first, second, third] = [GuestKey::new(1), GuestKey::new(2), GuestKey::new(3)];
+    purchase_tickets(&t, &[&first.addr, &second.addr, &third.addr])?;
+    t.mock.wait_seconds(1000)?;
+    let checkin = |guest: &GuestKey, nonce: u64| {
+        t.suite.cw_ave.execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: guest.checkin(&checkin_data(&t, vec![0], nonce)),
+            },
+            &[],
+        )
+    };
+    let occupancy = || -> anyhow::Result<u32> {
+        let res: Vec<OccupancyRes> = t.suite.cw_ave.query(&QueryMsg::Occupancy {})?;
+        Ok(res[0].occupancy)
+    };
+
+    // checkin is refused once the segment is at capacity
+    checkin(&first, 1)?;
+    checkin(&second, 1)?;
+    let err = checkin(&third, 1).unwrap_err();
+    assert!(format!("{:?}", err).contains("at capacity"));
+    let res: Vec<OccupancyRes> = t.suite.cw_ave.query(&QueryMsg::Occupancy {})?;
+    assert_eq!(
+        res,
+        vec![OccupancyRes {
+            segment_id: 0,
+            occupancy: 2,
+            capacity: Some(2),
+        }]
+    );
+
+    // guests can check themselves out, freeing up space
+    t.suite.cw_ave.call_as(&first.addr).execute(
+        &ExecuteMsg::CheckOut {
+            event_segment_ids: vec![0],
+        },
+        &[],
+    )?;
+    assert_eq!(occupancy()?, 1);
+    let err = t
+        .suite
+        .cw_ave
+        .call_as(&first.addr)
+        .execute(
+            &ExecuteMsg::CheckOut {
+                event_segment_ids: vec![0],
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("not inside"));
+    checkin(&third, 1)?;
+    assert!(checkin(&first, 2).is_err());
+
+    // only ushers can check guests out
+    let stranger = t.mock.addr_make("stranger");
+    let checkout_second = ExecuteMsg::CheckOutGuest {
+        ticket_addr: second.addr.to_string(),
+        event_segment_ids: vec![0],
+        gate: Some("south".to_string()),
+    };
+    assert!(t
+        .suite
+        .cw_ave
+        .call_as(&stranger)
+        .execute(&checkout_second, &[])
+        .is_err());
+    t.suite.cw_ave.execute(&checkout_second, &[])?;
+    let records: Vec<AttendanceRecord> =
+        t.suite.cw_ave.query(&QueryMsg::GuestAttendanceRecords {
+            guest: second.addr.to_string(),
+        })?;
+    let block = t.mock.block_info()?;
+    assert!(records[0].checked_out);
+    assert_eq!(
+        records[0].checkout,
+        Some(CheckOutRecord {
+            time: block.time,
+            height: block.height,
+            by: usher.clone(),
+            gate: Some("south".to_string()),
+        })
+    );
+    // checked out guests still count as having checked in
+    let status: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: second.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(status);
+
+    // guests re-enter as many times as their guest type allows
+    checkin(&first, 2)?;
+    assert_eq!(occupancy()?, 2);
+    let records: Vec<AttendanceRecord> =
+        t.suite.cw_ave.query(&QueryMsg::GuestAttendanceRecords {
+            guest: first.addr.to_string(),
+        })?;
+    assert_eq!(records[0].reentries, 1);
+    assert!(!records[0].checked_out);
+    // re-entries are added to the checkin log, keeping the first checkin
+    let checkins: Vec<CheckInLogEntry> = t.suite.cw_ave.query(&QueryMsg::GuestCheckins {
+        guest: first.addr.to_string(),
+        start_after: None,
+        limit: None,
+    })?;
+    assert_eq!(
+        checkins.iter().map(|c| c.seq).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    assert_eq!(records[0].checkin, Some(checkins[1].checkin.clone()));
+    let history: Vec<CheckInLogEntry> = t.suite.cw_ave.query(&QueryMsg::UsherCheckins {
+        usher: usher.to_string(),
+        start_after: None,
+        limit: None,
+    })?;
+    assert_eq!(
+        history
+            .iter()
+            .filter(|c| c.ticket_addr == first.addr.as_str())
+            .count(),
+        2
+    );
+    t.suite.cw_ave.call_as(&first.addr).execute(
+        &ExecuteMsg::CheckOut {
+            event_segment_ids: vec![0],
+        },
+        &[],
+    )?;
+    let err = checkin(&first, 3).unwrap_err();
+    assert!(format!("{:?}", err).contains("cannot re-enter"));
+    assert_eq!(occupancy()?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_poap_minted_on_checkin() -> anyhow::Result<()> {
+    let chain = MockBech32::new_with_chain_id("mock", "juno-1");
+    let poap = Cw721Poap::new(chain.clone());
+    poap.upload()?;
+    let cw721 = poap.code_id()?;
+    let t = TestEnv::setup_custom(chain, |now, msg| {
+        (msg.guest_details, msg.event_timeline) = default_event(now);
+        msg.guest_details[0].reentry = ReentryRule::Unlimited;
+        msg.poap = Some(PoapSettings {
+            cw721,
+            transferable: false,
+        });
+    })?;
+    let config: Config = t.suite.cw_ave.config()?;
+    poap.set_address(
+        &config
+            .poap_collection
+            .expect("poap collection instantiated"),
+    );
+    assert!(!poap.query::<bool>(&Cw721PoapQueryMsg::Transferable {})?);
+
+    let guest = GuestKey::new(1);
+    purchase_tickets(&t, &[&guest.addr])?;
+    t.mock.wait_seconds(1000)?;
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+
+    // a token is minted to the ticket for the segment checked into
+    let token_id = format!("0/{}", guest.addr);
+    let info: AllNftInfoResponse = poap.query(&Cw721PoapQueryMsg::AllNftInfo {
+        token_id: token_id.clone(),
+        include_expired: None,
+    })?;
+    assert_eq!(info.access.owner, guest.addr.to_string());
+    assert_eq!(
+        info.info.extension,
+        PoapMetadata {
+            event: "Test Event".to_string(),
+            segment: "Main Event".to_string(),
+            segment_id: 0,
+            guest_type: "VIP".to_string(),
+        }
+    );
+
+    // soulbound tokens cannot be transferred
+    let res = poap.call_as(&guest.addr).execute(
+        &Cw721PoapExecuteMsg::TransferNft {
+            recipient: t.mock.addr_make("friend").to_string(),
+            token_id,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // re-entries do not mint another token
+    t.suite.cw_ave.call_as(&guest.addr).execute(
+        &ExecuteMsg::CheckOut {
+            event_segment_ids: vec![0],
+        },
+        &[],
+    )?;
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 2)),
+        },
+        &[],
+    )?;
+    let count: NumTokensResponse = poap.query(&Cw721PoapQueryMsg::NumTokens {})?;
+    assert_eq!(count.count, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_with_homie_secret() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let [homie, revoked, claimer] = ["homie", "revoked", "claimer"].map(|n| t.mock.addr_make(n));
+    let buyer = purchase_tickets(&t, &[&homie, &revoked, &claimer])?;
+    let secret_hash = Binary::from(Sha256::digest(b"open sesame").to_vec());
+    let set_secret = |sender: &Addr, ticket: &Addr, secret_hash: Binary| {
+        t.suite.cw_ave.call_as(sender).execute(
+            &ExecuteMsg::SetHomieCheckinSecret {
+                ticket_addr: ticket.to_string(),
+                secret_hash,
+            },
+            &[],
+        )
+    };
+    let checkin = |ticket: &Addr, secret: &str| {
+        t.suite.cw_ave.execute(
+            &ExecuteMsg::CheckInWithHomieSecret {
+                ticket_addr: ticket.to_string(),
+                secret: secret.to_string(),
+                event_segment_ids: vec![0],
+                gate: None,
+            },
+            &[],
+        )
+    };
+    let commitment = |ticket: &Addr| -> anyhow::Result<Option<HomieCheckinSecret>> {
+        Ok(t.suite.cw_ave.query(&QueryMsg::HomieCheckinSecret {
+            ticket_addr: ticket.to_string(),
+        })?)
+    };
+
+    // the purchaser holds tickets reserved for homies, and commits to a sha256 hash
+    assert!(set_secret(&homie, &homie, secret_hash.clone()).is_err());
+    assert!(set_secret(&buyer, &homie, Binary::from(b"open sesame")).is_err());
+    for ticket in [&homie, &revoked, &claimer] {
+        set_secret(&buyer, ticket, secret_hash.clone())?;
+    }
+    assert_eq!(commitment(&homie)?.unwrap().set_by, buyer);
+
+    // only ushers with the correct secret can checkin the ticket
+    t.mock.wait_seconds(1000)?;
+    let stranger = t.mock.addr_make("stranger");
+    let res = t.suite.cw_ave.call_as(&stranger).execute(
+        &ExecuteMsg::CheckInWithHomieSecret {
+            ticket_addr: homie.to_string(),
+            secret: "open sesame".to_string(),
+            event_segment_ids: vec![0],
+            gate: None,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    assert!(checkin(&homie, "open says me").is_err());
+    checkin(&homie, "open sesame")?;
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: homie.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(checked_in);
+
+    // secrets are single use
+    assert!(commitment(&homie)?.is_none());
+    assert!(checkin(&homie, "open sesame").is_err());
+
+    // revoked secrets cannot checkin
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::RevokeHomieCheckinSecret {
+            ticket_addr: revoked.to_string(),
+        },
+        &[],
+    )?;
+    assert!(checkin(&revoked, "open sesame").is_err());
+
+    // once a homie claims their ticket, the purchaser's secret no longer applies
+    t.suite.cw_ave.call_as(&claimer).execute(
+        &ExecuteMsg::ClaimTicketReservedByHomie {
+            homie_addr: buyer.to_string(),
+        },
+        &[],
+    )?;
+    assert!(commitment(&claimer)?.is_none());
+    assert!(set_secret(&buyer, &claimer, secret_hash.clone()).is_err());
+    set_secret(&claimer, &claimer, secret_hash)?;
+    checkin(&claimer, "open sesame")?;
+
+    Ok(())
+}
+
+#[test]
+fn test_resale_orderbook() -> anyhow::Result<()> {
+    let t = TestEnv::setup_custom(
+        MockBech32::new_with_chain_id("mock", "juno-1"),
+        |now, msg| {
+            (msg.guest_details, msg.event_timeline) = default_event(now);
+            msg.resale = Some(ResaleSettings {
+                royalty_bps: 500,
+                max_price_bps: Some(12000),
+            });
+        },
+    )?;
+    let curator = t.mock.sender_addr();
+    let guest = GuestKey::new(1);
+    let new_guest = GuestKey::new(2);
+    // the ticket is reserved for a homie, so its purchaser holds it
+    let seller = purchase_tickets(&t, &[&guest.addr])?;
+    let list = |sender: &Addr, price: Coin| {
+        t.suite.cw_ave.call_as(sender).execute(
+            &ExecuteMsg::ListTicketForResale {
+                ticket_addr: guest.addr.to_string(),
+                price,
+            },
+            &[],
+        )
+    };
+
+    // tickets sold from now on cost more, which does not raise the cap of tickets already sold
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::UpdateEvent {
+            title: None,
+            description: None,
+            add_guest_types: vec![],
+            update_guest_types: vec![GuestTypeUpdate {
+                guest_weight: 1,
+                total_ticket_limit: None,
+                max_ticket_limit: None,
+                ticket_cost: Some(vec![coin(2000000, "ujuno"), coin(2000000, "uatom")]),
+                cw20_ticket_cost: None,
+            }],
+            reschedule_segments: vec![],
+        },
+        &[],
+    )?;
+
+    assert!(list(&guest.addr, coin(1100000, "ujuno")).is_err());
+    // resale prices are capped relative to the price paid, converted to other accepted denoms
+    assert!(list(&seller, coin(1200001, "ujuno")).is_err());
+    assert!(list(&seller, coin(1200001, "uatom")).is_err());
+    assert!(list(&seller, coin(1000000, "uosmo")).is_err());
+    list(&seller, coin(1200000, "ujuno"))?;
+    assert!(list(&seller, coin(1100000, "ujuno")).is_err());
+
+    // only the seller can cancel a listing
+    let stranger = t.mock.addr_make("stranger");
+    let cancel = ExecuteMsg::CancelResaleListing {
+        ticket_addr: guest.addr.to_string(),
+    };
+    assert!(t
+        .suite
+        .cw_ave
+        .call_as(&stranger)
+        .execute(&cancel, &[])
+        .is_err());
+    t.suite.cw_ave.call_as(&seller).execute(&cancel, &[])?;
+    list(&seller, coin(1100000, "ujuno"))?;
+    let listings: Vec<ResaleListingRes> = t.suite.cw_ave.query(&QueryMsg::ResaleListings {
+        start_after: None,
+        limit: None,
+    })?;
+    assert_eq!(listings.len(), 1);
+    assert_eq!(listings[0].listing.seller, seller);
+    assert_eq!(listings[0].listing.price, coin(1100000, "ujuno"));
+
+    // listed tickets cannot checkin or be refunded
+    t.mock.wait_seconds(1000)?;
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let res = t.suite.cw_ave.call_as(&seller).execute(
+        &ExecuteMsg::RefundUnconfirmedTickets {
+            guests: vec![guest.addr.to_string()],
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    let buyer = t
+        .mock
+        .addr_make_with_balance("resale_buyer", coins(1200000, "ujuno"))?;
+    let buy = ExecuteMsg::BuyResaleTicket {
+        ticket_addr: guest.addr.to_string(),
+        new_ticket_addr: new_guest.addr.to_string(),
+    };
+    assert!(t
+        .suite
+        .cw_ave
+        .call_as(&buyer)
+        .execute(&buy, &coins(1000000, "ujuno"))
+        .is_err());
+
+    // the seller is paid minus the curator royalty, and the buyer gets their change
+    let curator_balance = t.mock.query_balance(&curator, "ujuno")?;
+    t.suite
+        .cw_ave
+        .call_as(&buyer)
+        .execute(&buy, &coins(1200000, "ujuno"))?;
+    assert_eq!(
+        t.mock.query_balance(&seller, "ujuno")?,
+        Uint128::new(1045000)
+    );
+    assert_eq!(
+        t.mock.query_balance(&curator, "ujuno")?,
+        curator_balance + Uint128::new(55000)
+    );
+    assert_eq!(t.mock.query_balance(&buyer, "ujuno")?, Uint128::new(100000));
+
+    // guest membership moved to the new ticket address, which the buyer now holds
+    let config: Config = t.suite.cw_ave.config()?;
+    let guest_group = Cw420::new(t.mock.clone());
+    guest_group.set_address(&config.event_guest_contract);
+    let member: MemberResponse = guest_group.query(&Cw420QueryMsg::Member {
+        addr: guest.addr.to_string(),
+        at_height: None,
+    })?;
+    assert_eq!(member.weight, None);
+    let member: MemberResponse = guest_group.query(&Cw420QueryMsg::Member {
+        addr: new_guest.addr.to_string(),
+        at_height: None,
+    })?;
+    assert_eq!(member.weight, Some(1));
+    let receipt: TicketReceipt = t.suite.cw_ave.query(&QueryMsg::TicketReceipt {
+        ticket_addr: new_guest.addr.to_string(),
+    })?;
+    assert_eq!(receipt.purchaser, buyer);
+    let listings: Vec<ResaleListingRes> = t.suite.cw_ave.query(&QueryMsg::ResaleListings {
+        start_after: None,
+        limit: None,
+    })?;
+    assert!(listings.is_empty());
+
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: new_guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_rejects_mismatched_key() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let guest = GuestKey::new(1);
+    let imposter = GuestKey::new(2);
+    purchase_tickets(&t, &[&guest.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    // a valid signature from a key that does not belong to the ticket address
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: imposter.sign_for(&guest.addr, &checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // the pubkey of the ticket address, with a signature from another key
+    let mut checkin = imposter.sign_for(&guest.addr, &checkin_data(&t, vec![0], 2));
+    checkin.pubkey = guest.pubkey();
+    let res = t
+        .suite
+        .cw_ave
+        .execute(&ExecuteMsg::CheckInGuest { checkin }, &[]);
+    assert!(res.is_err());
+
+    // a ticket address without a ticket
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: imposter.checkin(&checkin_data(&t, vec![0], 3)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: guest.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(!checked_in);
+
+    Ok(())
+}
+
+#[test]
+fn test_update_ticket_address_moves_attendance() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let old = GuestKey::new(1);
+    let new = GuestKey::new(2);
+    let buyer = purchase_tickets(&t, &[&old.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    // only the purchaser of a homie ticket can move it
+    let res = t
+        .suite
+        .cw_ave
+        .call_as(&t.mock.addr_make("stranger"))
+        .execute(
+            &ExecuteMsg::UpdateTicketAddress {
+                new_ticket_addr: None,
+                replace_homies_ticket: vec![ReplaceHomieTicket {
+                    old: old.addr.to_string(),
+                    new: new.addr.to_string(),
+                }],
+            },
+            &[],
+        );
+    assert!(res.is_err());
+
+    t.suite.cw_ave.call_as(&buyer).execute(
+        &ExecuteMsg::UpdateTicketAddress {
+            new_ticket_addr: None,
+            replace_homies_ticket: vec![ReplaceHomieTicket {
+                old: old.addr.to_string(),
+                new: new.addr.to_string(),
+            }],
+        },
+        &[],
+    )?;
+
+    // the old key can no longer checkin, the new one can
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: old.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: new.checkin(&checkin_data(&t, vec![0], 2)),
+        },
+        &[],
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_signature_replay_protection() -> anyhow::Result<()> {
+    let t = TestEnv::setup_with(|now| {
+        (
+            vec![GuestDetails {
+                guest_type: "Weekend".to_string(),
+                guest_weight: 1,
+                max_ticket_limit: 5,
+                total_ticket_limit: 100,
+                ticket_cost: vec![coin(1000000, "ujuno")],
+                cw20_ticket_cost: vec![],
+                overbooking_limit: 0,
+                reentry: ReentryRule::Never,
+                event_segment_access: EventSegmentAccessType::AnyOfSpecificSegments {
+                    ids: vec![0, 1],
+                },
+            }],
+            vec![
+                EventSegment {
+                    stage_description: "Day 1".to_string(),
+                    start: now.plus_seconds(1000),
+                    end: now.plus_seconds(2000),
+                    escrow_weight: None,
+                    early_checkin_grace: None,
+                    late_checkin_grace: None,
+                    capacity: None,
+                },
+                EventSegment {
+                    stage_description: "Day 2".to_string(),
+                    start: now.plus_seconds(2000),
+                    end: now.plus_seconds(3000),
+                    escrow_weight: None,
+                    early_checkin_grace: None,
+                    late_checkin_grace: None,
+                    capacity: None,
+                },
+            ],
+        )
+    })?;
+    let guest = GuestKey::new(1);
+    purchase_tickets(&t, &[&guest.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    // signatures made for another event contract
+    let mut data = checkin_data(&t, vec![0], 1);
+    data.event_contract_addr = t.mock.addr_make("other_event").to_string();
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&data),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // signatures made for another usher
+    let mut data = checkin_data(&t, vec![0], 1);
+    data.usher_wallet_addr = t.mock.addr_make("other_usher").to_string();
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&data),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // signatures cannot be valid for longer than the max validity
+    let mut data = checkin_data(&t, vec![0], 1);
+    data.expires_at = t.mock.block_info()?.time.plus_seconds(3600);
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&data),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    // expired signatures are rejected
+    let expiring = guest.checkin(&checkin_data(&t, vec![0], 1));
+    t.mock.wait_seconds(301)?;
+    let res = t
+        .suite
+        .cw_ave
+        .execute(&ExecuteMsg::CheckInGuest { checkin: expiring }, &[]);
+    assert!(res.is_err());
+
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+
+    // a nonce cannot be reused, even for a different segment
+    t.mock.wait_seconds(1000)?;
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![1], 1)),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![1], 2)),
+        },
+        &[],
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_homies_tickets() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let purchaser = GuestKey::new(1);
+    let homie = GuestKey::new(2);
+    let claimed = GuestKey::new(3);
+    let stranger = GuestKey::new(4);
+    purchase_tickets_as(
+        &t,
+        &purchaser.addr,
+        &[&purchaser.addr, &homie.addr, &claimed.addr],
+    )?;
+    purchase_tickets(&t, &[&stranger.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    // once claimed, the purchaser can no longer checkin this homie
+    t.suite.cw_ave.call_as(&claimed.addr).execute(
+        &ExecuteMsg::ClaimTicketReservedByHomie {
+            homie_addr: purchaser.addr.to_string(),
+        },
+        &[],
+    )?;
+
+    let mut data = checkin_data(&t, vec![0], 1);
+    data.homies_tickets = vec![
+        homie.addr.to_string(),
+        claimed.addr.to_string(),
+        stranger.addr.to_string(),
+    ];
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: purchaser.checkin(&data),
+        },
+        &[],
+    )?;
+
+    let results = res.event_attr_values("wasm", "checkin_result");
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0], format!("{}:checked_in:0", purchaser.addr));
+    assert_eq!(results[1], format!("{}:checked_in:0", homie.addr));
+    assert!(results[2].starts_with(&format!("{}:failed:", claimed.addr)));
+    assert!(results[3].starts_with(&format!("{}:failed:", stranger.addr)));
+
+    for (guest, expected) in [
+        (&purchaser, true),
+        (&homie, true),
+        (&claimed, false),
+        (&stranger, false),
+    ] {
+        let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+            guest: guest.addr.to_string(),
+            event_stage_id: 0,
+        })?;
+        assert_eq!(checked_in, expected);
+    }
+
+    // homies can still checkin on their own
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: claimed.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+
+    // a group checkin where no ticket can be checked in fails
+    let mut data = checkin_data(&t, vec![0], 2);
+    data.homies_tickets = vec![homie.addr.to_string()];
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: purchaser.checkin(&data),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_batch_checkin_guests() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let first = GuestKey::new(1);
+    let second = GuestKey::new(2);
+    let imposter = GuestKey::new(3);
+    purchase_tickets(&t, &[&first.addr, &second.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    let checkins = vec![
+        first.checkin(&checkin_data(&t, vec![0], 1)),
+        imposter.sign_for(&second.addr, &checkin_data(&t, vec![0], 1)),
+        second.checkin(&checkin_data(&t, vec![0], 2)),
+    ];
+
+    // only ushers can checkin guests
+    let res = t
+        .suite
+        .cw_ave
+        .call_as(&t.mock.addr_make("stranger"))
+        .execute(
+            &ExecuteMsg::BatchCheckInGuests {
+                checkins: checkins.clone(),
+                mode: BatchCheckInMode::SkipInvalid,
+            },
+            &[],
+        );
+    assert!(res.is_err());
+
+    // the whole batch fails on the first invalid checkin
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::BatchCheckInGuests {
+            checkins: checkins.clone(),
+            mode: BatchCheckInMode::FailFast,
+        },
+        &[],
+    );
+    assert!(res.is_err());
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: first.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(!checked_in);
+
+    // invalid checkins are skipped & reported
+    let res = t.suite.cw_ave.execute(
+        &ExecuteMsg::BatchCheckInGuests {
+            checkins,
+            mode: BatchCheckInMode::SkipInvalid,
+        },
+        &[],
+    )?;
+    let skipped = res.event_attr_values("wasm", "checkin_skipped");
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].starts_with(&format!("1:{}:", second.addr)));
+    assert_eq!(
+        res.event_attr_values("wasm", "ticket_addr"),
+        vec![first.addr.to_string(), second.addr.to_string()]
+    );
+
+    for guest in [&first, &second] {
+        let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+            guest: guest.addr.to_string(),
+            event_stage_id: 0,
+        })?;
+        assert!(checked_in);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_time_windows() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let early = GuestKey::new(1);
+    let late = GuestKey::new(2);
+    purchase_tickets(&t, &[&early.addr, &late.addr])?;
+
+    // only the curator can set grace periods
+    let res = t
+        .suite
+        .cw_ave
+        .call_as(&t.mock.addr_make("stranger"))
+        .execute(
+            &ExecuteMsg::UpdateCheckinGracePeriods {
+                segment_id: 0,
+                early_checkin_grace: Some(300),
+                late_checkin_grace: Some(600),
+            },
+            &[],
+        );
+    assert!(res.is_err());
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::UpdateCheckinGracePeriods {
+            segment_id: 0,
+            early_checkin_grace: Some(300),
+            late_checkin_grace: Some(600),
+        },
+        &[],
+    )?;
+
+    // segment runs from 1000s to 2000s after setup, checkin opens 300s early
+    t.mock.wait_seconds(600)?;
+    let err = t
+        .suite
+        .cw_ave
+        .execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: early.checkin(&checkin_data(&t, vec![0], 1)),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("too early"));
+
+    t.mock.wait_seconds(150)?;
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: early.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+
+    // checkin closes 600s after the segment ends
+    t.mock.wait_seconds(1900)?;
+    let err = t
+        .suite
+        .cw_ave
+        .execute(
+            &ExecuteMsg::CheckInGuest {
+                checkin: late.checkin(&checkin_data(&t, vec![0], 1)),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("too late"));
+
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: late.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(!checked_in);
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_ed25519_guest() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let guest = GuestKey::ed25519(1);
+    purchase_tickets(&t, &[&guest.addr])?;
+    // checkin opens when the event segment starts
+    t.mock.wait_seconds(1000)?;
+
+    t.suite.cw_ave.execute(
+        &ExecuteMsg::CheckInGuest {
+            checkin: guest.checkin(&checkin_data(&t, vec![0], 1)),
+        },
+        &[],
+    )?;
+    let checked_in: bool = t.suite.cw_ave.query(&QueryMsg::GuestAttendanceStatus {
+        guest: guest.addr.to_string(),
+        event_stage_id: 0,
+    })?;
+    assert!(checked_in);
+
+    Ok(())
+}
+
+#[test]
+fn test_pubkey_address_vectors() -> anyhow::Result<()> {
+    let api = MockApi::default().with_prefix("cosmos");
+    let any = |type_url: &str, value: Vec<u8>| -> Binary {
+        Any {
+            type_url: type_url.to_string(),
+            value,
+        }
+        .encode_to_vec()
+        .into()
+    };
+    let key = |b64: &str| Binary::from_base64(b64).unwrap().to_vec();
+
+    // pubkeys with the addresses the cosmos-sdk derives for them
+    let vectors = [
+        (
+            CheckInSignatureScheme::Adr036Secp256k1 {},
+            any(
+                "/cosmos.crypto.secp256k1.PubKey",
+                secp256k1::PubKey {
+                    key: key("AtQaCqFnshaZQp6rIkvAPyzThvCvXSDO+9AzbxVErqJP"),
+                }
+                .encode_to_vec(),
+            ),
+            "cosmos1h806c7khnvmjlywdrkdgk2vrayy2mmvf9rxk2r",
+        ),
+        (
+            CheckInSignatureScheme::Adr036Ed25519 {},
+            any(
+                "/cosmos.crypto.ed25519.PubKey",
+                ed25519::PubKey {
+                    key: key("Eu5vWB/lVnOh6eE4Kggp4yB1oKpHY8lovFJuGFLnjJU="),
+                }
+                .encode_to_vec(),
+            ),
+            "cosmos1pfq05em6sfkls66ut4m2257p7qwlk448h8mysz",
+        ),
+        (
+            // the P-256 generator point
+            CheckInSignatureScheme::WebAuthnSecp256r1 {
+                authenticator_data: Binary::default(),
+                client_data_json: String::new(),
+            },
+            any(
+                "/cosmos.crypto.secp256r1.PubKey",
+                secp256r1::PubKey {
+                    key: key("A2sX0fLhLEJH+Lzm5WOkQPJ3A32BLeszoPShOUXYmMKW"),
+                }
+                .encode_to_vec(),
+            ),
+            "cosmos1552pdl8c2rns85k4rz8kzkn579q605epn7486w37w4pk8tyh30ws2tr56c",
+        ),
+    ];
+    for (scheme, pubkey, addr) in vectors {
+        let canonical = pubkey_to_canonical(&decode_pubkey(&pubkey, &scheme)?, &scheme);
+        assert_eq!(api.addr_humanize(&canonical)?.as_str(), addr);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_checkin_signature_scheme_vectors() -> anyhow::Result<()> {
+    let t = TestEnv::setup()?;
+    let api = MockApi::default().with_prefix("mock");
+    let data = checkin_data(&t, vec![0], 1);
+    let rp_id = Some(WEBAUTHN_RP_ID);
+
+    for guest in [GuestKey::new(1), GuestKey::ed25519(1), GuestKey::passkey(1)] {
+        let checkin = guest.checkin(&data);
+        verify_checkin_signature(&api, &checkin, rp_id)?;
+
+        // signatures do not carry over to different checkin data
+        let mut tampered = checkin.clone();
+        tampered.signed_data = guest.checkin(&checkin_data(&t, vec![0], 2)).signed_data;
+        assert!(verify_checkin_signature(&api, &tampered, rp_id).is_err());
+
+        // nor to another ticket address
+        let other = GuestKey::new(2);
+        assert!(
+            verify_checkin_signature(&api, &guest.sign_for(&other.addr, &data), rp_id).is_err()
+        );
+    }
+
+    // secp256k1 is used when no scheme is set
+    let mut checkin = GuestKey::new(1).checkin(&data);
+    assert_eq!(checkin.scheme, None);
+    checkin.scheme = Some(CheckInSignatureScheme::Adr036Secp256k1 {});
+    verify_checkin_signature(&api, &checkin, rp_id)?;
+
+    // pubkeys must match the scheme
+    let mut checkin = GuestKey::ed25519(1).checkin(&data);
+    checkin.scheme = None;
+    assert!(verify_checkin_signature(&api, &checkin, rp_id).is_err());
+
+    // webauthn client data must be an assertion of the checkin sign doc
+    let passkey = GuestKey::passkey(1);
+    let checkin = passkey.checkin(&data);
+    // events without a relying party refuse passkey checkins
+    assert!(verify_checkin_signature(&api, &checkin, None).is_err());
+    let Some(CheckInSignatureScheme::WebAuthnSecp256r1 {
+        authenticator_data,
+        client_data_json,
+    }) = checkin.scheme.clone()
+    else {
+        panic!("passkey checkin should use webauthn");
+    };
+    for client_data_json in [
+        client_data_json.replace("webauthn.get", "webauthn.create"),
+        client_data_json.replace("https://ave.example", "https://other.example"),
+        r#"{"type":"webauthn.get","challenge":"b3RoZXI"}"#.to_string(),
+    ] {
+        let mut bad = checkin.clone();
+        bad.scheme = Some(CheckInSignatureScheme::WebAuthnSecp256r1 {
+            authenticator_data: authenticator_data.clone(),
+            client_data_json,
+        });
+        assert!(verify_checkin_signature(&api, &bad, rp_id).is_err());
+    }
+
+    // authenticator data must be for the event's relying party, with the user present.
+    // each is signed by the passkey, so only the checks on it can fail
+    let GuestSigner::Passkey(key) = &passkey.signer else {
+        unreachable!()
+    };
+    let mut other_rp = authenticator_data.to_vec();
+    other_rp[..32].copy_from_slice(&Sha256::digest("other.example"));
+    let mut not_present = authenticator_data.to_vec();
+    not_present[32] &= !0x01;
+    for authenticator_data in [other_rp, not_present, authenticator_data[..36].to_vec()] {
+        let mut signed = authenticator_data.clone();
+        signed.extend(Sha256::digest(client_data_json.as_bytes()));
+        let signature: p256::ecdsa::Signature = key.sign(&signed);
+        let signature = signature.normalize_s().unwrap_or(signature);
+        let mut bad = checkin.clone();
+        bad.signature = signature.to_bytes().to_vec().into();
+        bad.scheme = Some(CheckInSignatureScheme::WebAuthnSecp256r1 {
+            authenticator_data: authenticator_data.into(),
+            client_data_json: client_data_json.clone(),
+        });
+        assert!(verify_checkin_signature(&api, &bad, rp_id).is_err());
+    }
+
+    Ok(())
+}